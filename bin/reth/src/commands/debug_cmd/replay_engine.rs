@@ -112,7 +112,7 @@ impl Command {
         #[cfg(feature = "optimism")]
         let payload_builder = reth_node_optimism::OptimismPayloadBuilder::new(
             provider_factory.chain_spec(),
-            reth_node_optimism::OptimismEvmConfig::default(),
+            reth_node_optimism::OptimismEvmConfig::new(&provider_factory.chain_spec()),
         );
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
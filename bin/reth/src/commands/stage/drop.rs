@@ -167,6 +167,7 @@ impl Command {
             }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
+                tx.clear::<tables::TransactionHashLocations>()?;
                 tx.put::<tables::StageCheckpoints>(
                     StageId::TransactionLookup.to_string(),
                     Default::default(),
@@ -3,10 +3,12 @@
 use reth_consensus::ConsensusError;
 use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_primitives::{BlockHash, BlockNumber, SealedBlock};
+use reth_storage_errors::db::DatabaseError;
 pub use reth_storage_errors::provider::ProviderError;
+use std::{mem::Discriminant, sync::Arc};
 
 /// Various error cases that can occur when a block violates tree assumptions.
-#[derive(Debug, Clone, Copy, thiserror::Error, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, thiserror::Error, Eq, PartialEq, Hash)]
 pub enum BlockchainTreeError {
     /// Thrown if the block number is lower than the last finalized block number.
     #[error("block number is lower than the last finalized block number #{last_finalized}")]
@@ -47,10 +49,26 @@ pub enum BlockchainTreeError {
     /// Thrown when trying to access genesis parent.
     #[error("genesis block has no parent")]
     GenesisBlockHasNoParent,
+    /// Thrown if the header of a block is known, but its body is missing from the body storage.
+    ///
+    /// This is a recoverable gap, the body can be requested again from peers.
+    #[error("body of block {block_hash} is missing")]
+    BlockBodyMissing {
+        /// The block hash of the block whose body is missing.
+        block_hash: BlockHash,
+    },
+    /// Thrown if the block was already inserted into the tree.
+    ///
+    /// The block was already processed, so this is safe to ignore.
+    #[error("block {block_hash} was already inserted")]
+    DuplicateBlock {
+        /// The block hash of the duplicate block.
+        block_hash: BlockHash,
+    },
 }
 
 /// Canonical Errors
-#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[derive(thiserror::Error, Debug, Clone)]
 pub enum CanonicalError {
     /// Error originating from validation operations.
     #[error(transparent)]
@@ -70,10 +88,82 @@ pub enum CanonicalError {
     /// Error indicating that a previous optimistic sync target was re-orged
     #[error("transaction error on revert: {0}")]
     OptimisticTargetRevert(BlockNumber),
+    /// Error originating from block execution that has no dedicated variant.
+    ///
+    /// The error is shared so that [`CanonicalError`] stays cloneable.
+    #[error(transparent)]
+    Execution(Arc<BlockExecutionError>),
+}
+
+impl PartialEq for CanonicalError {
+    /// Compares the errors by value.
+    ///
+    /// [`BlockExecutionError::Other`] errors can't be compared by value and are compared by their
+    /// message instead.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Validation(a), Self::Validation(b)) => a == b,
+            (Self::BlockchainTree(a), Self::BlockchainTree(b)) => a == b,
+            (Self::Provider(a), Self::Provider(b)) => a == b,
+            (Self::CanonicalRevert(a), Self::CanonicalRevert(b)) |
+            (Self::CanonicalCommit(a), Self::CanonicalCommit(b)) => a == b,
+            (Self::OptimisticTargetRevert(a), Self::OptimisticTargetRevert(b)) => a == b,
+            (Self::Execution(a), Self::Execution(b)) => execution_errors_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for CanonicalError {}
+
+/// Compares two [`BlockExecutionError`]s by value, [`BlockExecutionError::Other`] errors by their
+/// message.
+fn execution_errors_eq(a: &BlockExecutionError, b: &BlockExecutionError) -> bool {
+    match (a, b) {
+        (BlockExecutionError::Validation(a), BlockExecutionError::Validation(b)) => a == b,
+        (BlockExecutionError::Pruning(a), BlockExecutionError::Pruning(b)) => a == b,
+        (BlockExecutionError::Consensus(a), BlockExecutionError::Consensus(b)) => a == b,
+        (
+            BlockExecutionError::CanonicalRevert { inner: a },
+            BlockExecutionError::CanonicalRevert { inner: b },
+        ) |
+        (
+            BlockExecutionError::CanonicalCommit { inner: a },
+            BlockExecutionError::CanonicalCommit { inner: b },
+        ) => a == b,
+        (
+            BlockExecutionError::AppendChainDoesntConnect { chain_tip: a, other_chain_fork: c },
+            BlockExecutionError::AppendChainDoesntConnect { chain_tip: b, other_chain_fork: d },
+        ) => a == b && c == d,
+        (BlockExecutionError::LatestBlock(a), BlockExecutionError::LatestBlock(b)) => a == b,
+        (BlockExecutionError::Other(a), BlockExecutionError::Other(b)) => {
+            a.to_string() == b.to_string()
+        }
+        _ => false,
+    }
+}
+
+impl From<BlockExecutionError> for CanonicalError {
+    /// Converts the execution error into the matching variant.
+    ///
+    /// Validation, provider and canonical commit and revert errors are mapped to their dedicated
+    /// variants, all other errors are wrapped in [`CanonicalError::Execution`].
+    fn from(err: BlockExecutionError) -> Self {
+        match err {
+            BlockExecutionError::Validation(err) => Self::Validation(err),
+            BlockExecutionError::LatestBlock(err) => Self::Provider(err),
+            BlockExecutionError::CanonicalRevert { inner } => Self::CanonicalRevert(inner),
+            BlockExecutionError::CanonicalCommit { inner } => Self::CanonicalCommit(inner),
+            err => Self::Execution(Arc::new(err)),
+        }
+    }
 }
 
 impl CanonicalError {
     /// Returns `true` if the error is fatal.
+    ///
+    /// Fatal execution errors are converted into [`CanonicalError::CanonicalCommit`] and
+    /// [`CanonicalError::CanonicalRevert`], so a [`CanonicalError::Execution`] is never fatal.
     pub const fn is_fatal(&self) -> bool {
         matches!(self, Self::CanonicalCommit(_) | Self::CanonicalRevert(_))
     }
@@ -84,6 +174,17 @@ impl CanonicalError {
         matches!(self, Self::BlockchainTree(BlockchainTreeError::BlockHashNotFoundInChain { .. }))
     }
 
+    /// Returns `true` if the underlying error is a [`ProviderError::Database`] error, i.e. an
+    /// error of the database layer rather than a logical provider error.
+    pub const fn is_database_error(&self) -> bool {
+        matches!(self, Self::Provider(ProviderError::Database(_)))
+    }
+
+    /// Returns `true` if the underlying error matches [`CanonicalError::OptimisticTargetRevert`].
+    pub const fn is_optimistic_revert(&self) -> bool {
+        matches!(self, Self::OptimisticTargetRevert(_))
+    }
+
     /// Returns `Some(BlockNumber)` if the underlying error matches
     /// [`CanonicalError::OptimisticTargetRevert`].
     pub const fn optimistic_revert_block_number(&self) -> Option<BlockNumber> {
@@ -92,11 +193,27 @@ impl CanonicalError {
             _ => None,
         }
     }
+
+    /// Returns the block number carried by the error, if any.
+    ///
+    /// This is the re-orged block number of [`CanonicalError::OptimisticTargetRevert`], or the
+    /// block number of a [`BlockchainTreeError::BlockNumberNotFoundInChain`].
+    ///
+    /// [`BlockchainTreeError::PendingBlockIsFinalized`] only carries the last finalized block
+    /// number, not the number of the rejected block, so it returns `None`.
+    pub const fn block_number(&self) -> Option<BlockNumber> {
+        match self {
+            Self::OptimisticTargetRevert(block_number) |
+            Self::BlockchainTree(BlockchainTreeError::BlockNumberNotFoundInChain {
+                block_number,
+            }) => Some(*block_number),
+            _ => None,
+        }
+    }
 }
 
 /// Error thrown when inserting a block failed because the block is considered invalid.
-#[derive(thiserror::Error)]
-#[error(transparent)]
+#[derive(Clone)]
 pub struct InsertBlockError {
     inner: Box<InsertBlockErrorData>,
 }
@@ -153,6 +270,18 @@ impl InsertBlockError {
         let inner = *self.inner;
         (inner.block, inner.kind)
     }
+
+    /// Returns references to the block and the error kind.
+    #[inline]
+    pub const fn split_ref(&self) -> (&SealedBlock, &InsertBlockErrorKind) {
+        (&self.inner.block, &self.inner.kind)
+    }
+}
+
+impl std::fmt::Display for InsertBlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.inner, f)
+    }
 }
 
 impl std::fmt::Debug for InsertBlockError {
@@ -161,6 +290,13 @@ impl std::fmt::Debug for InsertBlockError {
     }
 }
 
+impl std::error::Error for InsertBlockError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.inner.kind)
+    }
+}
+
+#[derive(Clone)]
 struct InsertBlockErrorData {
     block: SealedBlock,
     kind: InsertBlockErrorKind,
@@ -208,7 +344,7 @@ impl InsertBlockErrorData {
 }
 
 /// All error variants possible when inserting a block
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum InsertBlockErrorKind {
     /// Failed to recover senders for the block
     #[error("failed to recover senders for block")]
@@ -226,8 +362,11 @@ pub enum InsertBlockErrorKind {
     #[error(transparent)]
     Provider(#[from] ProviderError),
     /// An internal error occurred, like interacting with the database.
+    ///
+    /// The error is shared so that clones keep the original error, see
+    /// [`InsertBlockErrorKind::is_transient`].
     #[error(transparent)]
-    Internal(#[from] Box<dyn std::error::Error + Send + Sync>),
+    Internal(Arc<dyn std::error::Error + Send + Sync>),
     /// Canonical error.
     #[error(transparent)]
     Canonical(#[from] CanonicalError),
@@ -236,7 +375,21 @@ pub enum InsertBlockErrorKind {
     BlockchainTree(BlockchainTreeError),
 }
 
+impl From<Box<dyn std::error::Error + Send + Sync>> for InsertBlockErrorKind {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
 impl InsertBlockErrorKind {
+    /// Returns a cheap token identifying the variant of the error, ignoring the inner error.
+    ///
+    /// This can be used to compare error kinds, e.g. in tests, without requiring the inner errors
+    /// to be comparable.
+    pub const fn discriminant(&self) -> Discriminant<Self> {
+        std::mem::discriminant(self)
+    }
+
     /// Returns true if the error is a tree error
     pub const fn is_tree_error(&self) -> bool {
         matches!(self, Self::Tree(_))
@@ -281,7 +434,7 @@ impl InsertBlockErrorKind {
     ///
     /// This is intended to be used to determine if the block should be marked as invalid.
     #[allow(clippy::match_same_arms)]
-    pub const fn is_invalid_block(&self) -> bool {
+    pub fn is_invalid_block(&self) -> bool {
         match self {
             Self::SenderRecovery | Self::Consensus(_) => true,
             // other execution errors that are considered internal errors
@@ -311,7 +464,9 @@ impl InsertBlockErrorKind {
                     BlockchainTreeError::BlockNumberNotFoundInChain { .. } |
                     BlockchainTreeError::BlockHashNotFoundInChain { .. } |
                     BlockchainTreeError::BlockBufferingFailed { .. } |
-                    BlockchainTreeError::GenesisBlockHasNoParent => false,
+                    BlockchainTreeError::GenesisBlockHasNoParent |
+                    BlockchainTreeError::BlockBodyMissing { .. } |
+                    BlockchainTreeError::DuplicateBlock { .. } => false,
                 }
             }
             Self::Provider(_) | Self::Internal(_) => {
@@ -319,17 +474,48 @@ impl InsertBlockErrorKind {
                 false
             }
             Self::Canonical(err) => match err {
+                // the state root of the block does not match the state root computed after
+                // executing it
+                CanonicalError::Provider(ProviderError::StateRootMismatch(_)) => true,
                 CanonicalError::BlockchainTree(_) |
                 CanonicalError::CanonicalCommit(_) |
                 CanonicalError::CanonicalRevert(_) |
                 CanonicalError::OptimisticTargetRevert(_) |
                 CanonicalError::Provider(_) => false,
                 CanonicalError::Validation(_) => true,
+                CanonicalError::Execution(err) => matches!(
+                    **err,
+                    BlockExecutionError::Validation(_) | BlockExecutionError::Consensus(_)
+                ),
             },
             Self::BlockchainTree(_) => false,
         }
     }
 
+    /// Returns true if the error is caused by a block body missing from the body storage.
+    pub const fn is_block_body_missing(&self) -> bool {
+        matches!(
+            self,
+            Self::Tree(BlockchainTreeError::BlockBodyMissing { .. }) |
+                Self::BlockchainTree(BlockchainTreeError::BlockBodyMissing { .. }) |
+                Self::Canonical(CanonicalError::BlockchainTree(
+                    BlockchainTreeError::BlockBodyMissing { .. }
+                ))
+        )
+    }
+
+    /// Returns true if the error is caused by a block that was already inserted into the tree.
+    pub const fn is_duplicate_block(&self) -> bool {
+        matches!(
+            self,
+            Self::Tree(BlockchainTreeError::DuplicateBlock { .. }) |
+                Self::BlockchainTree(BlockchainTreeError::DuplicateBlock { .. }) |
+                Self::Canonical(CanonicalError::BlockchainTree(
+                    BlockchainTreeError::DuplicateBlock { .. }
+                ))
+        )
+    }
+
     /// Returns true if this is a block pre merge error.
     pub const fn is_block_pre_merge(&self) -> bool {
         matches!(
@@ -350,6 +536,33 @@ impl InsertBlockErrorKind {
         matches!(self, Self::Internal(_))
     }
 
+    /// Returns true if the error is transient, like a database I/O failure, and inserting the
+    /// block again may succeed.
+    ///
+    /// Structural, consensus and validation errors are never transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Internal(err) => {
+                let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err.as_ref());
+                while let Some(err) = source {
+                    if err.is::<std::io::Error>() {
+                        return true
+                    }
+                    if let Some(err) = err.downcast_ref::<DatabaseError>() {
+                        return is_transient_database_error(err)
+                    }
+                    source = err.source();
+                }
+                false
+            }
+            Self::Provider(ProviderError::Database(err)) |
+            Self::Canonical(CanonicalError::Provider(ProviderError::Database(err))) => {
+                is_transient_database_error(err)
+            }
+            _ => false,
+        }
+    }
+
     /// Returns the error if it is a tree error
     pub const fn as_tree_error(&self) -> Option<BlockchainTreeError> {
         match self {
@@ -366,6 +579,14 @@ impl InsertBlockErrorKind {
         }
     }
 
+    /// Returns the error if it is a provider error
+    pub const fn as_provider_error(&self) -> Option<&ProviderError> {
+        match self {
+            Self::Provider(err) => Some(err),
+            _ => None,
+        }
+    }
+
     /// Returns the error if it is an execution error
     pub const fn as_execution_error(&self) -> Option<&BlockExecutionError> {
         match self {
@@ -374,3 +595,17 @@ impl InsertBlockErrorKind {
         }
     }
 }
+
+/// Returns true if the database error is caused by a failed operation that may succeed if retried,
+/// as opposed to e.g. corrupted data.
+const fn is_transient_database_error(err: &DatabaseError) -> bool {
+    matches!(
+        err,
+        DatabaseError::Read(_) |
+            DatabaseError::Write(_) |
+            DatabaseError::Delete(_) |
+            DatabaseError::Commit(_) |
+            DatabaseError::InitTx(_) |
+            DatabaseError::InitCursor(_)
+    )
+}
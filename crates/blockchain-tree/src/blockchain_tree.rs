@@ -19,9 +19,10 @@ use reth_primitives::{
     SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, B256, U256,
 };
 use reth_provider::{
-    BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, ChainSpecProvider, ChainSplit,
-    ChainSplitTarget, DisplayBlocksChain, HeaderProvider, ProviderError, StaticFileProviderFactory,
+    BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    ChainSpecProvider, ChainSplit, ChainSplitTarget, DisplayBlocksChain, HeaderProvider,
+    ProviderError, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_api::{MetricEvent, MetricEventsSender};
@@ -315,6 +316,9 @@ where
     ///
     /// If the block's parent block is unknown, this returns [`BlockStatus::Disconnected`] and the
     /// block will be buffered until the parent block is inserted and then attached to sidechain
+    ///
+    /// If the block is already part of a sidechain, this returns
+    /// [`BlockchainTreeError::DuplicateBlock`], which callers should treat as benign.
     #[instrument(level = "trace", skip_all, fields(block = ?block.num_hash()), target = "blockchain_tree", ret)]
     fn try_insert_validated_block(
         &mut self,
@@ -323,6 +327,12 @@ where
     ) -> Result<BlockStatus, InsertBlockErrorKind> {
         debug_assert!(self.validate_block(&block).is_ok(), "Block must be validated");
 
+        // the block is already part of a side chain, there is nothing left to do
+        let block_hash = block.hash();
+        if self.block_indices().get_block_chain_id(&block_hash).is_some() {
+            return Err(BlockchainTreeError::DuplicateBlock { block_hash }.into())
+        }
+
         let parent = block.parent_num_hash();
 
         // check if block parent can be found in any side chain.
@@ -359,7 +369,6 @@ where
         // insert block inside unconnected block buffer. Delaying its execution.
         self.state.buffered_blocks.insert_block(block.clone());
 
-        let block_hash = block.hash();
         // find the lowest ancestor of the block in the buffer to return as the missing parent
         // this shouldn't return None because that only happens if the block was evicted, which
         // shouldn't happen right after insertion
@@ -927,7 +936,10 @@ where
             let _ = self
                 .try_insert_validated_block(block, BlockValidationKind::SkipStateRootValidation)
                 .map_err(|err| {
-                    debug!(target: "blockchain_tree", %err, "Failed to insert buffered block");
+                    // a block that was already inserted is not a failure
+                    if !err.is_duplicate_block() {
+                        debug!(target: "blockchain_tree", %err, "Failed to insert buffered block");
+                    }
                     err
                 });
         }
@@ -1322,6 +1334,18 @@ where
 
         let tip = provider_rw.last_block_number()?;
         let revert_range = (revert_until + 1)..=tip;
+
+        // The reverted blocks are reconstructed from their bodies, a missing body would otherwise
+        // silently drop the block from the reconstructed side chain.
+        for block_number in revert_range.clone() {
+            if provider_rw.block_body_indices(block_number)?.is_none() {
+                let block_hash = provider_rw
+                    .block_hash(block_number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                return Err(BlockchainTreeError::BlockBodyMissing { block_hash }.into())
+            }
+        }
+
         info!(target: "blockchain_tree", "REORG: revert canonical from database by unwinding chain blocks {:?}", revert_range);
         // read block and execution result from database. and remove traces of block from tables.
         let blocks_and_execution = provider_rw
@@ -1873,6 +1897,83 @@ mod tests {
         assert_eq!(state_root, block5.state_root);
     }
 
+    #[test]
+    fn test_insert_duplicate_block() {
+        let data = BlockchainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let genesis = data.genesis;
+
+        let externals = setup_externals(vec![exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+        tree.make_canonical(B256::ZERO).unwrap();
+        tree.finalize_block(10).unwrap();
+
+        assert_eq!(
+            tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid(BlockAttachment::Canonical))
+        );
+
+        // inserting the block again is answered from the tree without executing it again
+        assert_eq!(
+            tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap(),
+            InsertPayloadOk::AlreadySeen(BlockStatus::Valid(BlockAttachment::Canonical))
+        );
+
+        // inserting the validated block directly reports the duplicate
+        let err = tree
+            .try_insert_validated_block(block1.clone(), BlockValidationKind::Exhaustive)
+            .unwrap_err();
+        assert!(err.is_duplicate_block());
+        assert!(!err.is_invalid_block());
+
+        // the block is still only part of a single chain
+        TreeTester::default()
+            .with_chain_num(1)
+            .with_block_to_chain(HashMap::from([(block1.hash(), 0.into())]))
+            .with_fork_to_child(HashMap::from([(
+                block1.parent_hash,
+                HashSet::from([block1.hash()]),
+            )]))
+            .assert(&tree);
+    }
+
+    #[test]
+    fn revert_canonical_with_missing_body() {
+        let data = BlockchainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let genesis = data.genesis;
+
+        let externals = setup_externals(vec![exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+        tree.make_canonical(B256::ZERO).unwrap();
+        tree.finalize_block(10).unwrap();
+
+        tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.make_canonical(block1.hash()).unwrap();
+
+        let provider = tree.externals.provider_factory.provider_rw().unwrap();
+        provider.tx_ref().delete::<tables::BlockBodyIndices>(block1.number, None).unwrap();
+        provider.commit().unwrap();
+
+        let err = tree.revert_canonical_from_database(10).unwrap_err();
+        assert_matches!(
+            err,
+            CanonicalError::BlockchainTree(BlockchainTreeError::BlockBodyMissing { block_hash })
+                if block_hash == block1.hash()
+        );
+        // nothing was unwound
+        assert_eq!(
+            tree.externals.provider_factory.provider().unwrap().last_block_number().unwrap(),
+            block1.number
+        );
+    }
+
     #[test]
     fn test_side_chain_fork() {
         let data = BlockchainTestData::default_from_number(11);
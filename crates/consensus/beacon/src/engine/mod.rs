@@ -1227,9 +1227,17 @@ where
 
         let block_hash = block.hash();
         let start = Instant::now();
-        let status = self
+        let status = match self
             .blockchain
-            .insert_block_without_senders(block.clone(), BlockValidationKind::Exhaustive)?;
+            .insert_block_without_senders(block.clone(), BlockValidationKind::Exhaustive)
+        {
+            Ok(status) => status,
+            // the block was already inserted and validated
+            Err(error) if error.kind().is_duplicate_block() => {
+                return Ok(PayloadStatus::new(PayloadStatusEnum::Valid, Some(block_hash)))
+            }
+            Err(error) => return Err(error),
+        };
 
         let elapsed = start.elapsed();
         let mut latest_valid_hash = None;
@@ -1697,7 +1705,7 @@ where
                                 Err(BeaconOnNewPayloadError::Internal(Box::new(error.clone())));
                             let _ = tx.send(response);
                             return Err(RethError::Canonical(error))
-                        } else if error.optimistic_revert_block_number().is_some() {
+                        } else if error.is_optimistic_revert() {
                             // engine already set the pipeline unwind target on
                             // `try_make_sync_target_canonical`
                             PayloadStatus::from_status(PayloadStatusEnum::Syncing)
@@ -1760,6 +1768,10 @@ where
                             _ => (),
                         }
                     }
+                    Err(err) if err.kind().is_duplicate_block() => {
+                        // the block was already inserted, there is nothing left to do
+                        trace!(target: "consensus::engine", %err, "Downloaded block was already inserted");
+                    }
                     Err(err) => {
                         warn!(target: "consensus::engine", %err, "Failed to insert downloaded block");
                         if err.kind().is_invalid_block() {
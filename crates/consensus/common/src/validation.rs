@@ -37,6 +37,21 @@ pub fn validate_header_base_fee(
     Ok(())
 }
 
+/// Ensures the number of transactions in a block does not exceed the maximum number of
+/// transactions per block of the chain, if there is one.
+#[inline]
+pub const fn validate_block_transaction_count(
+    count: usize,
+    max_transactions_per_block: Option<usize>,
+) -> Result<(), ConsensusError> {
+    if let Some(limit) = max_transactions_per_block {
+        if count > limit {
+            return Err(ConsensusError::TransactionCountExceedsLimit { count, limit })
+        }
+    }
+    Ok(())
+}
+
 /// Validate a block without regard for state:
 ///
 /// - Compares the ommer hash in the block header to the block body
@@ -531,4 +546,19 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn transaction_count_exceeds_limit() {
+        // no limit
+        assert_eq!(validate_block_transaction_count(10, None), Ok(()));
+
+        // at the limit
+        assert_eq!(validate_block_transaction_count(10, Some(10)), Ok(()));
+
+        // exceeding the limit
+        assert_eq!(
+            validate_block_transaction_count(11, Some(10)),
+            Err(ConsensusError::TransactionCountExceedsLimit { count: 11, limit: 10 })
+        );
+    }
 }
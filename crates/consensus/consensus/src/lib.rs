@@ -139,6 +139,15 @@ pub enum ConsensusError {
         gas_limit: u64,
     },
 
+    /// Error when the number of transactions in the block exceeds the transaction count limit.
+    #[error("block has {count} transactions, exceeding the limit of {limit}")]
+    TransactionCountExceedsLimit {
+        /// The number of transactions in the block.
+        count: usize,
+        /// The maximum number of transactions allowed in the block.
+        limit: usize,
+    },
+
     /// Error when block gas used doesn't match expected value
     #[error("block gas used mismatch: {gas}; gas spent by each transaction: {gas_spent_by_tx:?}")]
     BlockGasUsed {
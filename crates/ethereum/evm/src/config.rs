@@ -1,4 +1,4 @@
-use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_chainspec::{ChainHardforks, ChainSpec, EthereumHardforks};
 use reth_ethereum_forks::{EthereumHardfork, Head};
 
 /// Returns the spec id at the given timestamp.
@@ -22,37 +22,42 @@ pub fn revm_spec_by_timestamp_after_merge(
 
 /// return `revm_spec` from spec configuration.
 pub fn revm_spec(chain_spec: &ChainSpec, block: &Head) -> revm_primitives::SpecId {
-    if chain_spec.fork(EthereumHardfork::Prague).active_at_head(block) {
+    revm_spec_by_hardforks(&chain_spec.hardforks, block)
+}
+
+/// Returns the `revm_spec` active at the given head according to the given hardforks.
+///
+/// This is [`revm_spec`] for chain specifications other than [`ChainSpec`], e.g. a
+/// [`ChainSpecLike`](reth_evm::ChainSpecLike).
+pub fn revm_spec_by_hardforks(hardforks: &ChainHardforks, block: &Head) -> revm_primitives::SpecId {
+    if hardforks.fork(EthereumHardfork::Prague).active_at_head(block) {
         revm_primitives::PRAGUE
-    } else if chain_spec.fork(EthereumHardfork::Cancun).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Cancun).active_at_head(block) {
         revm_primitives::CANCUN
-    } else if chain_spec.fork(EthereumHardfork::Shanghai).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Shanghai).active_at_head(block) {
         revm_primitives::SHANGHAI
-    } else if chain_spec.fork(EthereumHardfork::Paris).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Paris).active_at_head(block) {
         revm_primitives::MERGE
-    } else if chain_spec.fork(EthereumHardfork::London).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::London).active_at_head(block) {
         revm_primitives::LONDON
-    } else if chain_spec.fork(EthereumHardfork::Berlin).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Berlin).active_at_head(block) {
         revm_primitives::BERLIN
-    } else if chain_spec.fork(EthereumHardfork::Istanbul).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Istanbul).active_at_head(block) {
         revm_primitives::ISTANBUL
-    } else if chain_spec.fork(EthereumHardfork::Petersburg).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Petersburg).active_at_head(block) {
         revm_primitives::PETERSBURG
-    } else if chain_spec.fork(EthereumHardfork::Byzantium).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Byzantium).active_at_head(block) {
         revm_primitives::BYZANTIUM
-    } else if chain_spec.fork(EthereumHardfork::SpuriousDragon).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::SpuriousDragon).active_at_head(block) {
         revm_primitives::SPURIOUS_DRAGON
-    } else if chain_spec.fork(EthereumHardfork::Tangerine).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Tangerine).active_at_head(block) {
         revm_primitives::TANGERINE
-    } else if chain_spec.fork(EthereumHardfork::Homestead).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Homestead).active_at_head(block) {
         revm_primitives::HOMESTEAD
-    } else if chain_spec.fork(EthereumHardfork::Frontier).active_at_head(block) {
+    } else if hardforks.fork(EthereumHardfork::Frontier).active_at_head(block) {
         revm_primitives::FRONTIER
     } else {
-        panic!(
-            "invalid hardfork chainspec: expected at least one hardfork, got {:?}",
-            chain_spec.hardforks
-        )
+        panic!("invalid hardfork chainspec: expected at least one hardfork, got {hardforks:?}")
     }
 }
 
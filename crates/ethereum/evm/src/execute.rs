@@ -5,25 +5,27 @@ use crate::{
     EthEvmConfig,
 };
 use reth_chainspec::{ChainSpec, EthereumHardforks, MAINNET};
+use reth_consensus_common::validation::validate_block_transaction_count;
 use reth_ethereum_consensus::validate_block_post_execution;
 use reth_evm::{
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
-        BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
+        BlockExecutor, BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
+        TransactionSnapshots,
     },
     ConfigureEvm,
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    BlockNumber, BlockWithSenders, EthereumHardfork, Header, Receipt, Request, U256,
+    BlockNumber, BlockWithSenders, EthereumHardfork, Header, Receipt, Request, B256, U256,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::{
     batch::{BlockBatchRecord, BlockExecutorStats},
     db::states::bundle_state::BundleRetention,
     state_change::{
-        apply_beacon_root_contract_call, apply_blockhashes_update,
-        apply_withdrawal_requests_contract_call, post_block_balance_increments,
+        apply_pre_block_calls, apply_withdrawal_requests_contract_call,
+        post_block_balance_increments,
     },
     Evm, State,
 };
@@ -44,7 +46,8 @@ pub struct EthExecutorProvider<EvmConfig = EthEvmConfig> {
 impl EthExecutorProvider {
     /// Creates a new default ethereum executor provider.
     pub fn ethereum(chain_spec: Arc<ChainSpec>) -> Self {
-        Self::new(chain_spec, Default::default())
+        let evm_config = EthEvmConfig::new(&chain_spec);
+        Self::new(chain_spec, evm_config)
     }
 
     /// Returns a new provider for the mainnet.
@@ -137,38 +140,37 @@ where
     ///
     /// It does __not__ apply post-execution changes that do not require an [EVM](Evm), for that see
     /// [`EthBlockExecutor::post_execution`].
+    ///
+    /// The gas used by the executed transactions is accumulated in `cumulative_gas_used`.
     fn execute_state_transitions<Ext, DB>(
         &self,
         block: &BlockWithSenders,
+        parent_beacon_block_root: Option<B256>,
         mut evm: Evm<'_, Ext, &mut State<DB>>,
+        cumulative_gas_used: &mut u64,
+        snapshots: &mut TransactionSnapshots,
     ) -> Result<EthExecuteOutput, BlockExecutionError>
     where
         DB: Database,
         DB::Error: Into<ProviderError> + std::fmt::Display,
     {
-        // apply pre execution changes
-        apply_beacon_root_contract_call(
-            &self.chain_spec,
-            block.timestamp,
-            block.number,
-            block.parent_beacon_block_root,
-            &mut evm,
-        )?;
-        apply_blockhashes_update(
-            evm.db_mut(),
-            &self.chain_spec,
-            block.timestamp,
-            block.number,
-            block.parent_hash,
+        // validate the transaction count against the limit of the evm configuration
+        validate_block_transaction_count(
+            block.body.len(),
+            self.evm_config.max_transactions_per_block(),
         )?;
 
+        // apply pre execution changes
+        apply_pre_block_calls(&self.chain_spec, &block.header, parent_beacon_block_root, &mut evm)?;
+
         // execute transactions
-        let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
         for (sender, transaction) in block.transactions_with_sender() {
+            snapshots.take(evm.db_mut(), *cumulative_gas_used);
+
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
-            let block_available_gas = block.header.gas_limit - cumulative_gas_used;
+            let block_available_gas = block.header.gas_limit - *cumulative_gas_used;
             if transaction.gas_limit() > block_available_gas {
                 return Err(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
                     transaction_gas_limit: transaction.gas_limit(),
@@ -197,7 +199,7 @@ where
             evm.db_mut().commit(state);
 
             // append gas used
-            cumulative_gas_used += result.gas_used();
+            *cumulative_gas_used += result.gas_used();
 
             // Push transaction changeset and calculate header bloom filter for receipt.
             receipts.push(
@@ -207,7 +209,7 @@ where
                     // Success flag was added in `EIP-658: Embedding transaction status code in
                     // receipts`.
                     success: result.is_success(),
-                    cumulative_gas_used,
+                    cumulative_gas_used: *cumulative_gas_used,
                     // convert to reth log
                     logs: result.into_logs(),
                     ..Default::default()
@@ -228,7 +230,7 @@ where
             vec![]
         };
 
-        Ok(EthExecuteOutput { receipts, requests, gas_used: cumulative_gas_used })
+        Ok(EthExecuteOutput { receipts, requests, gas_used: *cumulative_gas_used })
     }
 }
 
@@ -243,12 +245,21 @@ pub struct EthBlockExecutor<EvmConfig, DB> {
     executor: EthEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// The gas used by the transactions of the current block that were executed so far.
+    gas_used: u64,
+    /// The state snapshots taken before the transactions of the current block.
+    snapshots: TransactionSnapshots,
 }
 
 impl<EvmConfig, DB> EthBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: EthEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: EthEvmExecutor { chain_spec, evm_config },
+            state,
+            gas_used: 0,
+            snapshots: TransactionSnapshots::new(),
+        }
     }
 
     #[inline]
@@ -281,7 +292,7 @@ where
             &mut block_env,
             self.chain_spec(),
             header,
-            total_difficulty,
+            Some(total_difficulty),
         );
 
         EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default())
@@ -297,6 +308,7 @@ where
         &mut self,
         block: &BlockWithSenders,
         total_difficulty: U256,
+        parent_beacon_block_root: Option<B256>,
     ) -> Result<EthExecuteOutput, BlockExecutionError> {
         // 1. prepare state on new block
         self.on_new_block(&block.header);
@@ -305,7 +317,13 @@ where
         let env = self.evm_env_for_block(&block.header, total_difficulty);
         let output = {
             let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_state_transitions(block, evm)
+            self.executor.execute_state_transitions(
+                block,
+                parent_beacon_block_root,
+                evm,
+                &mut self.gas_used,
+                &mut self.snapshots,
+            )
         }?;
 
         // 3. apply post execution changes
@@ -316,6 +334,9 @@ where
 
     /// Apply settings before a new block is executed.
     pub(crate) fn on_new_block(&mut self, header: &Header) {
+        self.gas_used = 0;
+        self.snapshots.clear();
+
         // Set state clear flag if the block is after the Spurious Dragon hardfork.
         let state_clear_flag = self.chain_spec().is_spurious_dragon_active_at_block(header.number);
         self.state.set_state_clear_flag(state_clear_flag);
@@ -370,14 +391,58 @@ where
     ///
     /// State changes are committed to the database.
     fn execute(mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
-        let BlockExecutionInput { block, total_difficulty } = input;
-        let EthExecuteOutput { receipts, requests, gas_used } =
-            self.execute_without_verification(block, total_difficulty)?;
+        self.execute_block(input)
+    }
+}
+
+impl<EvmConfig, DB> BlockExecutor<DB> for EthBlockExecutor<EvmConfig, DB>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error: Into<ProviderError> + std::fmt::Display>,
+{
+    fn execute_block(&mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
+        let BlockExecutionInput { block, total_difficulty, parent_beacon_block_root } = input;
+        let EthExecuteOutput { receipts, requests, gas_used } = self.execute_without_verification(
+            block,
+            total_difficulty,
+            parent_beacon_block_root.or(block.parent_beacon_block_root),
+        )?;
+
+        let blob_gas_used = self
+            .chain_spec()
+            .is_cancun_active_at_timestamp(block.timestamp)
+            .then(|| block.blob_transactions_iter().filter_map(|tx| tx.blob_gas_used()).sum());
+
+        // the snapshots do not track merged transitions
+        self.snapshots.clear();
 
         // NOTE: we need to merge keep the reverts for the bundle retention
         self.state.merge_transitions(BundleRetention::Reverts);
 
-        Ok(BlockExecutionOutput { state: self.state.take_bundle(), receipts, requests, gas_used })
+        Ok(BlockExecutionOutput {
+            state: self.state.take_bundle(),
+            receipts,
+            requests,
+            gas_used,
+            blob_gas_used,
+        })
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    fn enable_transaction_snapshots(&mut self) {
+        self.snapshots.enable();
+    }
+
+    fn revert_to(&mut self, tx_index: usize) -> Result<(), BlockExecutionError> {
+        self.gas_used = self.snapshots.revert_to(&mut self.state, tx_index)?;
+        Ok(())
+    }
+
+    fn into_state(self) -> State<DB> {
+        self.state
     }
 }
 
@@ -413,9 +478,13 @@ where
     type Error = BlockExecutionError;
 
     fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
-        let BlockExecutionInput { block, total_difficulty } = input;
+        let BlockExecutionInput { block, total_difficulty, parent_beacon_block_root } = input;
         let EthExecuteOutput { receipts, requests, gas_used: _ } =
-            self.executor.execute_without_verification(block, total_difficulty)?;
+            self.executor.execute_without_verification(
+                block,
+                total_difficulty,
+                parent_beacon_block_root.or(block.parent_beacon_block_root),
+            )?;
 
         validate_block_post_execution(block, self.executor.chain_spec(), &receipts, &requests)?;
 
@@ -465,9 +534,11 @@ mod tests {
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
     };
     use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_evm::execute::DebugBlock;
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, Transaction, TxKind, TxLegacy, B256,
+        keccak256, public_key_to_address, Account, Address, Block, Transaction, TxKind, TxLegacy,
+        B256,
     };
     use reth_revm::{
         database::StateProviderDatabase, test_utils::StateProviderTest, TransitionState,
@@ -519,6 +590,54 @@ mod tests {
         EthExecutorProvider { chain_spec, evm_config: Default::default() }
     }
 
+    /// Returns a state with a funded sender, the sender and a block with the given number of value
+    /// transfers from it.
+    fn create_value_transfers(
+        chain_spec: &ChainSpec,
+        gas_limit: u64,
+        transfers: u64,
+    ) -> (StateProviderTest, Address, BlockWithSenders) {
+        let mut db = StateProviderTest::default();
+        let secp = Secp256k1::new();
+        let sender_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        db.insert_account(
+            sender_address,
+            Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let body = (0..transfers)
+            .map(|nonce| {
+                sign_tx_with_key_pair(
+                    sender_key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: 7,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(Address::with_last_byte(0x42)),
+                        value: U256::from(1),
+                        input: Bytes::default(),
+                    }),
+                )
+            })
+            .collect();
+        let header = Header {
+            number: 1,
+            timestamp: 1,
+            gas_limit,
+            base_fee_per_gas: Some(7),
+            ..Header::default()
+        };
+        let block = Block { header, body, ommers: vec![], withdrawals: None, requests: None }
+            .with_recovered_senders()
+            .unwrap();
+
+        (db, sender_address, block)
+    }
+
     #[test]
     fn eip_4788_non_genesis_call() {
         let mut header =
@@ -582,6 +701,7 @@ mod tests {
                     senders: vec![],
                 },
                 U256::ZERO,
+                header.parent_beacon_block_root,
             )
             .unwrap();
 
@@ -1407,4 +1527,93 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn gas_used_after_failed_transaction() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        // the block gas limit only fits two of the three transfers
+        let (db, _, block) = create_value_transfers(&chain_spec, 50_000, 3);
+
+        let mut executor = executor_provider(chain_spec).executor(StateProviderDatabase::new(&db));
+        assert!(executor.execute_block((&block, U256::ZERO).into()).is_err());
+        assert_eq!(executor.gas_used(), 42_000);
+    }
+
+    #[test]
+    fn revert_to_transaction_snapshot() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        // the block gas limit only fits two of the three transfers
+        let (db, sender_address, block) = create_value_transfers(&chain_spec, 50_000, 3);
+
+        let mut executor = executor_provider(chain_spec).executor(StateProviderDatabase::new(&db));
+        executor.enable_transaction_snapshots();
+        assert!(executor.execute_block((&block, U256::ZERO).into()).is_err());
+
+        // no snapshot after the failing transaction
+        assert!(executor.revert_to(3).is_err());
+
+        for tx_index in (0..3).rev() {
+            executor.revert_to(tx_index).unwrap();
+            assert_eq!(executor.gas_used(), 21_000 * tx_index as u64);
+            let sender = executor.state_mut().basic(sender_address).unwrap().unwrap();
+            assert_eq!(sender.nonce, tx_index as u64);
+        }
+
+        // the snapshots of the reverted transactions are discarded
+        assert!(executor.revert_to(1).is_err());
+    }
+
+    #[test]
+    fn revert_to_requires_snapshots() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+        let (db, _, block) = create_value_transfers(&chain_spec, 50_000, 3);
+
+        let mut executor = executor_provider(chain_spec).executor(StateProviderDatabase::new(&db));
+        assert!(executor.execute_block((&block, U256::ZERO).into()).is_err());
+        assert!(executor.revert_to(0).is_err());
+    }
+
+    #[test]
+    fn debug_block_matches_full_execution() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+        let (db, sender_address, block) = create_value_transfers(&chain_spec, 1_000_000, 3);
+        let recipient = Address::with_last_byte(0x42);
+
+        let debug = DebugBlock::new(
+            EthEvmConfig::default(),
+            chain_spec.clone(),
+            StateProviderDatabase::new(&db),
+        );
+        let sealed = block.clone().seal_slow();
+
+        // the state before each transaction matches a full execution of the block cut off there
+        for tx_index in 0..=block.body.len() {
+            let state = debug.at_transaction(&sealed, U256::ZERO, tx_index).unwrap();
+
+            let mut truncated = block.clone();
+            truncated.block.body.truncate(tx_index);
+            truncated.senders.truncate(tx_index);
+            let mut executor =
+                executor_provider(chain_spec.clone()).executor(StateProviderDatabase::new(&db));
+            executor.execute_without_verification(&truncated, U256::ZERO, None).unwrap();
+
+            let addresses = state.keys().copied().chain([sender_address, recipient]);
+            for address in addresses.collect::<Vec<_>>() {
+                let expected = executor.state.basic(address).unwrap().unwrap_or_default();
+                let actual = match state.get(&address) {
+                    Some(account) => account.info.clone(),
+                    None => {
+                        StateProviderDatabase::new(&db).basic(address).unwrap().unwrap_or_default()
+                    }
+                };
+                assert_eq!(actual.balance, expected.balance, "balance of {address} at {tx_index}");
+                assert_eq!(actual.nonce, expected.nonce, "nonce of {address} at {tx_index}");
+            }
+        }
+
+        // out of bounds
+        assert!(debug.at_transaction(&sealed, U256::ZERO, block.body.len() + 1).is_err());
+    }
 }
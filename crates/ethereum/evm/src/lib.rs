@@ -12,14 +12,15 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
-use reth_chainspec::{ChainSpec, Head};
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use core::convert::Infallible;
+use reth_chainspec::{Chain, ChainSpec};
+use reth_evm::{head_for_header, ChainSpecLike, ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{Header, U256};
 use reth_revm::{Database, EvmBuilder};
 use revm_primitives::{AnalysisKind, CfgEnvWithHandlerCfg};
 
 mod config;
-pub use config::{revm_spec, revm_spec_by_timestamp_after_merge};
+pub use config::{revm_spec, revm_spec_by_hardforks, revm_spec_by_timestamp_after_merge};
 
 pub mod execute;
 
@@ -30,26 +31,40 @@ pub mod dao_fork;
 pub mod eip6110;
 
 /// Ethereum-related EVM configuration.
-#[derive(Debug, Clone, Copy, Default)]
-#[non_exhaustive]
-pub struct EthEvmConfig;
+///
+/// The default configuration is bound to Ethereum mainnet, use [`EthEvmConfig::new`] for other
+/// chains.
+#[derive(Debug, Clone, Copy)]
+pub struct EthEvmConfig {
+    /// The chain id of the chain spec the configuration was created for.
+    chain_id: u64,
+}
+
+impl EthEvmConfig {
+    /// Creates a new configuration for the given chain spec.
+    pub const fn new(chain_spec: &ChainSpec) -> Self {
+        Self { chain_id: chain_spec.chain().id() }
+    }
+}
+
+impl Default for EthEvmConfig {
+    fn default() -> Self {
+        Self { chain_id: Chain::mainnet().id() }
+    }
+}
 
 impl ConfigureEvmEnv for EthEvmConfig {
+    type Error = Infallible;
+
     fn fill_cfg_env(
         cfg_env: &mut CfgEnvWithHandlerCfg,
-        chain_spec: &ChainSpec,
+        chain_spec: &dyn ChainSpecLike,
         header: &Header,
-        total_difficulty: U256,
+        total_difficulty: Option<U256>,
     ) {
-        let spec_id = config::revm_spec(
-            chain_spec,
-            &Head {
-                number: header.number,
-                timestamp: header.timestamp,
-                difficulty: header.difficulty,
-                total_difficulty,
-                hash: Default::default(),
-            },
+        let spec_id = config::revm_spec_by_hardforks(
+            chain_spec.hardforks(),
+            &head_for_header(header, total_difficulty),
         );
 
         cfg_env.chain_id = chain_spec.chain().id();
@@ -57,6 +72,10 @@ impl ConfigureEvmEnv for EthEvmConfig {
 
         cfg_env.handler_cfg.spec_id = spec_id;
     }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
 }
 
 impl ConfigureEvm for EthEvmConfig {
@@ -73,12 +92,14 @@ impl ConfigureEvm for EthEvmConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_chainspec::ChainSpec;
+    use reth_chainspec::{ChainSpec, MAINNET, SEPOLIA};
+    use reth_evm::execute::BlockValidationError;
     use reth_primitives::{
         revm_primitives::{BlockEnv, CfgEnv, SpecId},
-        Header, U256,
+        Header, SealedBlock, B256, U256,
     };
-    use revm_primitives::CfgEnvWithHandlerCfg;
+    use reth_revm::{db::EmptyDB, inspectors::NoOpInspector};
+    use revm_primitives::{CfgEnvWithHandlerCfg, EnvWithHandlerCfg};
 
     #[test]
     #[ignore]
@@ -94,9 +115,177 @@ mod tests {
             &mut block_env,
             &chain_spec,
             &header,
-            total_difficulty,
+            Some(total_difficulty),
         );
 
         assert_eq!(cfg_env.chain_id, chain_spec.chain().id());
     }
+
+    #[test]
+    fn chain_id_of_chain_spec() {
+        assert_eq!(EthEvmConfig::default().chain_id(), MAINNET.chain().id());
+        assert_eq!(EthEvmConfig::new(&SEPOLIA).chain_id(), SEPOLIA.chain().id());
+    }
+
+    #[test]
+    fn test_next_block_env() {
+        let parent = Header {
+            number: 20_000_000,
+            timestamp: 1_720_000_000,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(10_000_000_000),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(0),
+            mix_hash: B256::with_last_byte(1),
+            ..Default::default()
+        };
+
+        let block_env = EthEvmConfig::default().next_block_env(&parent, 1_720_000_012, &*MAINNET);
+
+        assert_eq!(block_env.number, U256::from(20_000_001));
+        assert_eq!(block_env.timestamp, U256::from(1_720_000_012));
+        // gas used is exactly at target, so the basefee is unchanged
+        assert_eq!(block_env.basefee, U256::from(10_000_000_000u64));
+        assert_eq!(block_env.prevrandao, Some(parent.mix_hash));
+        assert_eq!(block_env.difficulty, U256::ZERO);
+        assert_eq!(block_env.get_blob_excess_gas(), Some(0));
+    }
+
+    #[test]
+    fn test_estimate_gas_ceiling() {
+        let evm_config = EthEvmConfig::default();
+        let parent = Header { number: 20_000_000, gas_limit: 30_000_000, ..Default::default() };
+        assert_eq!(evm_config.estimate_gas_ceiling(&parent, &*MAINNET), 30_029_295);
+
+        // the parent gas limit is doubled at the london transition
+        let parent = Header { number: 12_964_999, gas_limit: 15_000_000, ..Default::default() };
+        assert_eq!(evm_config.estimate_gas_ceiling(&parent, &*MAINNET), 30_029_295);
+
+        // the ceiling saturates instead of overflowing
+        let parent = Header { number: 12_964_999, gas_limit: u64::MAX, ..Default::default() };
+        assert_eq!(evm_config.estimate_gas_ceiling(&parent, &*MAINNET), u64::MAX);
+    }
+
+    #[test]
+    fn test_fill_block_env_with_parent() {
+        let parent = Header {
+            number: 20_000_000,
+            gas_limit: 30_000_000,
+            gas_used: 30_000_000,
+            base_fee_per_gas: Some(8_000_000_000),
+            ..Default::default()
+        };
+        let header = Header { number: 20_000_001, timestamp: 1_720_000_012, ..Default::default() };
+
+        let mut block_env = BlockEnv::default();
+        EthEvmConfig::default().fill_block_env_with_parent(
+            &mut block_env,
+            &*MAINNET,
+            &header,
+            &parent,
+            true,
+        );
+
+        // full parent block increases the basefee by 12.5%
+        assert_eq!(block_env.basefee, U256::from(9_000_000_000u64));
+        assert_eq!(block_env.number, U256::from(20_000_001));
+    }
+
+    #[test]
+    fn test_fill_block_env_from_block() {
+        let block = SealedBlock {
+            header: Header {
+                number: 20_000_000,
+                timestamp: 1_720_000_000,
+                mix_hash: B256::with_last_byte(1),
+                base_fee_per_gas: Some(7),
+                ..Default::default()
+            }
+            .seal_slow(),
+            ..Default::default()
+        };
+
+        let mut block_env = BlockEnv::default();
+        EthEvmConfig::default().fill_block_env_from_block(&mut block_env, &block, &*MAINNET);
+
+        assert_eq!(block_env.number, U256::from(20_000_000));
+        assert_eq!(block_env.basefee, U256::from(7));
+        assert_eq!(block_env.prevrandao, Some(block.mix_hash));
+        assert_eq!(block_env.difficulty, U256::ZERO);
+    }
+
+    #[test]
+    fn test_evm_ref() {
+        let db = EmptyDB::default();
+        let evm_config = EthEvmConfig::default();
+
+        // the same database can be shared by multiple EVMs
+        let first = evm_config.evm_ref(&db);
+        let second = evm_config.evm_ref(&db);
+        assert_eq!(first.spec_id(), second.spec_id());
+    }
+
+    #[test]
+    fn test_with_inspector() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::SHANGHAI),
+            BlockEnv { number: U256::from(17_034_870), ..Default::default() },
+            Default::default(),
+        );
+        let evm_config = EthEvmConfig::default();
+
+        let evm = evm_config.evm_with_env(EmptyDB::default(), env);
+        let evm = evm_config.with_inspector(evm, NoOpInspector);
+        assert_eq!(evm.spec_id(), SpecId::SHANGHAI);
+        assert_eq!(evm.block().number, U256::from(17_034_870));
+    }
+
+    #[test]
+    fn test_evm_with_env_handler_cfg() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::CANCUN),
+            BlockEnv::default(),
+            Default::default(),
+        );
+        let evm = EthEvmConfig::default().evm_with_env(EmptyDB::default(), env);
+        assert_eq!(evm.handler_cfg().spec_id, SpecId::CANCUN);
+
+        let (_, env) = evm.into_db_and_env_with_handler_cfg();
+        assert_eq!(env.handler_cfg.spec_id, SpecId::CANCUN);
+    }
+
+    #[test]
+    fn test_try_evm_with_env() {
+        let block = BlockEnv {
+            number: U256::from(20_000_000),
+            timestamp: U256::from(1_720_000_000),
+            ..Default::default()
+        };
+        let env_with_spec = |spec_id| {
+            EnvWithHandlerCfg::new_with_cfg_env(
+                CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), spec_id),
+                block.clone(),
+                Default::default(),
+            )
+        };
+        let evm_config = EthEvmConfig::default();
+
+        let evm = evm_config
+            .try_evm_with_env(EmptyDB::default(), env_with_spec(SpecId::CANCUN), &*MAINNET)
+            .unwrap();
+        assert_eq!(evm.spec_id(), SpecId::CANCUN);
+
+        let err = evm_config
+            .try_evm_with_env(EmptyDB::default(), env_with_spec(SpecId::SHANGHAI), &*MAINNET)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BlockValidationError::IncompatibleSpecId {
+                spec_id: SpecId::SHANGHAI,
+                active_spec_id: SpecId::CANCUN,
+                block_number: 20_000_000,
+            }
+        );
+    }
 }
@@ -99,7 +99,7 @@ where
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
-        let evm_config = EthEvmConfig::default();
+        let evm_config = EthEvmConfig::new(&chain_spec);
         let executor = EthExecutorProvider::new(chain_spec, evm_config);
 
         Ok((evm_config, executor))
@@ -17,7 +17,7 @@ use alloy_primitives::B256;
 use reth_consensus::ConsensusError;
 use reth_prune_types::PruneSegmentError;
 use reth_storage_errors::provider::ProviderError;
-use revm_primitives::EVMError;
+use revm_primitives::{EVMError, SpecId};
 
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, string::String};
@@ -44,6 +44,9 @@ pub enum BlockValidationError {
     #[error("incrementing balance in post execution failed")]
     IncrementBalanceFailed,
     /// Error when the state root does not match the expected value.
+    ///
+    /// A mismatch between the computed root and the block header root is reported as
+    /// [`ConsensusError::BodyStateRootDiff`], which carries both roots.
     #[error(transparent)]
     StateRoot(#[from] StateRootError),
     /// Error when transaction gas limit exceeds available block gas
@@ -90,6 +93,16 @@ pub enum BlockValidationError {
     /// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
     #[error(transparent)]
     BlockHashAccountLoadingFailed(#[from] ProviderError),
+    /// EVM error during [EIP-2935] history storage contract call.
+    ///
+    /// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+    #[error("failed to apply history storage contract call at {parent_block_hash}: {message}")]
+    BlockHashContractCall {
+        /// The parent block hash
+        parent_block_hash: Box<B256>,
+        /// The error message.
+        message: String,
+    },
     /// EVM error during withdrawal requests contract call [EIP-7002]
     ///
     /// [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
@@ -103,10 +116,21 @@ pub enum BlockValidationError {
     /// [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
     #[error("failed to decode deposit requests from receipts: {0}")]
     DepositRequestDecode(String),
+    /// Error when the spec id of the EVM environment is behind the spec id that is active for the
+    /// block according to the chain spec.
+    #[error("EVM spec id {spec_id:?} is behind the active spec id {active_spec_id:?} of block {block_number}")]
+    IncompatibleSpecId {
+        /// The spec id of the EVM environment.
+        spec_id: SpecId,
+        /// The spec id that is active for the block according to the chain spec.
+        active_spec_id: SpecId,
+        /// The number of the block.
+        block_number: u64,
+    },
 }
 
 /// `BlockExecutor` Errors
-#[derive(thiserror_no_std::Error, Debug)]
+#[derive(thiserror_no_std::Error, Debug, Clone)]
 pub enum BlockExecutionError {
     /// Validation error, transparently wrapping `BlockValidationError`
     #[error(transparent)]
@@ -143,9 +167,12 @@ pub enum BlockExecutionError {
     #[error(transparent)]
     LatestBlock(#[from] ProviderError),
     /// Arbitrary Block Executor Errors
+    ///
+    /// The error is shared so that cloning the error keeps the original error, which can still be
+    /// downcast.
     #[cfg(feature = "std")]
     #[error(transparent)]
-    Other(Box<dyn std::error::Error + Send + Sync>),
+    Other(std::sync::Arc<dyn std::error::Error + Send + Sync>),
 }
 
 impl BlockExecutionError {
@@ -155,13 +182,13 @@ impl BlockExecutionError {
     where
         E: std::error::Error + Send + Sync + 'static,
     {
-        Self::Other(Box::new(error))
+        Self::Other(std::sync::Arc::new(error))
     }
 
     /// Create a new [`BlockExecutionError::Other`] from a given message.
     #[cfg(feature = "std")]
     pub fn msg(msg: impl std::fmt::Display) -> Self {
-        Self::Other(msg.to_string().into())
+        Self::Other(Box::<dyn std::error::Error + Send + Sync>::from(msg.to_string()).into())
     }
 
     /// Returns the inner `BlockValidationError` if the error is a validation error.
@@ -1,3 +1,4 @@
+use reth_execution_errors::BlockExecutionError;
 use reth_primitives::{
     logs_bloom, Account, Address, BlockNumber, Bloom, Bytecode, Log, Receipt, Receipts, Requests,
     StorageEntry, B256, U256,
@@ -149,6 +150,27 @@ impl ExecutionOutcome {
         HashedPostState::from_bundle_state(&self.bundle.state)
     }
 
+    /// Computes the state root of the bundle state with a naive, in-memory trie construction.
+    ///
+    /// Only the accounts of the bundle state are taken into account, so this is only the actual
+    /// state root if the bundle holds the entire state, e.g. if execution started from an empty
+    /// or genesis-only state. This is meant as a cross-check for tests and fuzzing, see
+    /// [`StateRoot`](reth_trie::StateRoot) for computing the state root against the database.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn state_root_slow(&self) -> B256 {
+        reth_trie::test_utils::state_root(self.bundle.state.iter().filter_map(
+            |(address, account)| {
+                let info = account.info.clone()?;
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| !slot.present_value.is_zero())
+                    .map(|(key, slot)| (B256::from(*key), slot.present_value));
+                Some((*address, (Account::from(info), storage)))
+            },
+        ))
+    }
+
     /// Transform block number to the index of block.
     fn block_number_to_index(&self, block_number: BlockNumber) -> Option<usize> {
         if self.first_block > block_number {
@@ -297,6 +319,32 @@ impl ExecutionOutcome {
         self.requests.extend(other.requests);
     }
 
+    /// Merges the execution outcome of the blocks directly following this one, e.g. the outcome of
+    /// a segment executed in parallel, into this one.
+    ///
+    /// Returns an error if the first block of `other` does not directly follow the last block of
+    /// this outcome. Empty outcomes can always be merged.
+    pub fn merge(mut self, other: Self) -> Result<Self, BlockExecutionError> {
+        if self.is_empty() {
+            return Ok(other)
+        }
+        if other.is_empty() {
+            return Ok(self)
+        }
+
+        let expected_first_block = self.first_block + self.len() as u64;
+        if other.first_block != expected_first_block {
+            return Err(BlockExecutionError::msg(format!(
+                "execution outcome starting at block {} does not follow block {}",
+                other.first_block,
+                expected_first_block - 1
+            )))
+        }
+
+        self.extend(other);
+        Ok(self)
+    }
+
     /// Prepends present the state with the given `BundleState`.
     /// It adds changes from the given state but does not override any existing changes.
     ///
@@ -331,7 +379,9 @@ mod tests {
     use super::*;
     use alloy_eips::{eip6110::DepositRequest, eip7002::WithdrawalRequest};
     use alloy_primitives::{FixedBytes, LogData};
-    use reth_primitives::{Address, Receipts, Request, Requests, TxType, B256};
+    use reth_primitives::{
+        constants::EMPTY_ROOT_HASH, Address, Receipts, Request, Requests, TxType, B256,
+    };
     use std::collections::HashMap;
 
     #[test]
@@ -427,6 +477,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_state_root_slow() {
+        assert_eq!(ExecutionOutcome::default().state_root_slow(), EMPTY_ROOT_HASH);
+
+        let address = Address::new([1; 20]);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let bundle = BundleState::new(
+            vec![
+                (
+                    address,
+                    None,
+                    Some(account.into()),
+                    HashMap::from([
+                        (U256::from(1), (U256::ZERO, U256::from(5))),
+                        (U256::from(2), (U256::from(3), U256::ZERO)),
+                    ]),
+                ),
+                // destroyed accounts are not part of the state
+                (Address::new([2; 20]), Some(AccountInfo::default()), None, HashMap::default()),
+            ],
+            vec![],
+            vec![],
+        );
+        let outcome = ExecutionOutcome::new(bundle, Receipts::default(), 0, vec![]);
+
+        let expected = reth_trie::test_utils::state_root([(
+            address,
+            (account, [(B256::with_last_byte(1), U256::from(5))]),
+        )]);
+        assert_eq!(outcome.state_root_slow(), expected);
+    }
+
     #[test]
     fn test_block_number_to_index() {
         // Create a Receipts object with a vector of receipt vectors
@@ -709,6 +791,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_execution_outcome() {
+        let receipt = Receipt {
+            tx_type: TxType::Legacy,
+            cumulative_gas_used: 46913,
+            logs: vec![],
+            success: true,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: Some(18),
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: Some(34),
+        };
+        let receipts = Receipts { receipt_vec: vec![vec![Some(receipt.clone())]] };
+
+        let lower = ExecutionOutcome {
+            bundle: Default::default(),
+            receipts: receipts.clone(),
+            requests: vec![],
+            first_block: 123,
+        };
+        let higher = ExecutionOutcome { first_block: 124, ..lower.clone() };
+
+        // Merging non contiguous outcomes fails.
+        assert!(lower.clone().merge(lower.clone()).is_err());
+        assert!(higher.clone().merge(lower.clone()).is_err());
+
+        // Merging empty outcomes returns the other outcome.
+        assert_eq!(ExecutionOutcome::default().merge(lower.clone()).unwrap(), lower);
+        assert_eq!(lower.clone().merge(ExecutionOutcome::default()).unwrap(), lower);
+
+        assert_eq!(
+            lower.merge(higher).unwrap(),
+            ExecutionOutcome {
+                bundle: Default::default(),
+                receipts: Receipts {
+                    receipt_vec: vec![vec![Some(receipt.clone())], vec![Some(receipt)]]
+                },
+                requests: vec![],
+                first_block: 123,
+            }
+        );
+    }
+
     #[test]
     fn test_split_at_execution_outcome() {
         // Create a random receipt object
@@ -0,0 +1,260 @@
+//! Builder for creating an EVM with a database and environment.
+
+use revm::{
+    handler::register::HandleRegister, inspector_handle_register, Database, Evm, EvmBuilder,
+    GetInspector,
+};
+use revm_primitives::{EnvWithHandlerCfg, SpecId};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Builder for creating an EVM with a database, an external context and an optional environment.
+///
+/// Additional [`HandleRegister`]s can be attached with
+/// [`with_handler_register`](RethEvmBuilder::with_handler_register), they are applied in order
+/// once the EVM is built.
+#[derive(Debug)]
+pub struct RethEvmBuilder<DB: Database, EXT = ()> {
+    /// The database to use for the EVM.
+    db: DB,
+    /// The environment to use for the EVM.
+    env: Option<EnvWithHandlerCfg>,
+    /// The spec id to use for the EVM, overriding the one of the environment.
+    spec_id: Option<SpecId>,
+    /// The external context for the EVM.
+    external_context: EXT,
+    /// Additional handler registers, applied in order on build.
+    handler_registers: Vec<HandleRegister<EXT, DB>>,
+}
+
+impl<DB, EXT> RethEvmBuilder<DB, EXT>
+where
+    DB: Database,
+{
+    /// Create a new EVM builder with the given database and external context.
+    pub const fn new(db: DB, external_context: EXT) -> Self {
+        Self { db, env: None, spec_id: None, external_context, handler_registers: Vec::new() }
+    }
+
+    /// Set the environment for the EVM, including the spec id.
+    ///
+    /// The spec id of the [`EnvWithHandlerCfg`] is applied on build, so the built EVM matches the
+    /// given environment.
+    pub fn with_env(mut self, env: EnvWithHandlerCfg) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Set the spec id of the EVM, without requiring a full environment.
+    ///
+    /// This takes precedence over the spec id of the environment set with
+    /// [`with_env`](RethEvmBuilder::with_env).
+    pub const fn with_spec_id(mut self, spec_id: SpecId) -> Self {
+        self.spec_id = Some(spec_id);
+        self
+    }
+
+    /// Append a handler register that is applied to the EVM handler on build.
+    ///
+    /// Registers are applied in the order they were added, after the inspector register when
+    /// building with [`build_with_inspector`](RethEvmBuilder::build_with_inspector).
+    pub fn with_handler_register(mut self, f: HandleRegister<EXT, DB>) -> Self {
+        self.handler_registers.push(f);
+        self
+    }
+
+    /// Consumes the builder without building the EVM and returns the database and the external
+    /// context.
+    pub fn into_db(self) -> (DB, EXT) {
+        (self.db, self.external_context)
+    }
+
+    /// Build the EVM with the given database and environment.
+    pub fn build<'a>(self) -> Evm<'a, EXT, DB>
+    where
+        DB: 'a,
+        EXT: 'a,
+    {
+        let Self { db, env, spec_id, external_context, handler_registers } = self;
+        let builder = EvmBuilder::default().with_db(db).with_external_context(external_context);
+        Self::finish(builder, env, spec_id, handler_registers)
+    }
+
+    /// Build the EVM with the given database and environment, using the external context as the
+    /// inspector.
+    pub fn build_with_inspector<'a>(self) -> Evm<'a, EXT, DB>
+    where
+        DB: 'a,
+        EXT: GetInspector<DB> + 'a,
+    {
+        let Self { db, env, spec_id, external_context, handler_registers } = self;
+        let builder = EvmBuilder::default()
+            .with_db(db)
+            .with_external_context(external_context)
+            .append_handler_register(inspector_handle_register);
+        Self::finish(builder, env, spec_id, handler_registers)
+    }
+
+    /// Applies the environment, the spec id and the additional handler registers and builds the
+    /// EVM.
+    fn finish<'a, STAGE>(
+        mut builder: EvmBuilder<'a, STAGE, EXT, DB>,
+        env: Option<EnvWithHandlerCfg>,
+        spec_id: Option<SpecId>,
+        handler_registers: Vec<HandleRegister<EXT, DB>>,
+    ) -> Evm<'a, EXT, DB> {
+        if let Some(env) = env {
+            builder = builder.with_spec_id(env.spec_id()).with_env(env.env);
+        }
+        if let Some(spec_id) = spec_id {
+            builder = builder.with_spec_id(spec_id);
+        }
+
+        let mut evm = builder.build();
+        for register in handler_registers {
+            evm.handler.append_handler_register_plain(register);
+        }
+        evm
+    }
+}
+
+impl<DB, EXT> Default for RethEvmBuilder<DB, EXT>
+where
+    DB: Database + Default,
+    EXT: Default,
+{
+    fn default() -> Self {
+        Self::new(DB::default(), EXT::default())
+    }
+}
+
+impl<'a, DB, EXT> RethEvmBuilder<DB, &'a EXT>
+where
+    DB: Database,
+{
+    /// Create a new EVM builder with the given database and a shared reference to the external
+    /// context.
+    ///
+    /// This allows an inspector to be shared by multiple EVMs, e.g. to accumulate traces through
+    /// interior mutability, as long as `&EXT` implements [`GetInspector`].
+    pub const fn with_external_context_ref(db: DB, external_context: &'a EXT) -> Self
+    where
+        &'a EXT: GetInspector<DB>,
+    {
+        Self::new(db, external_context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::EmptyDB,
+        handler::register::EvmHandler,
+        inspectors::NoOpInspector,
+        interpreter::{CallInputs, CallOutcome},
+        EvmContext, Inspector,
+    };
+    use revm_primitives::{CfgEnvWithHandlerCfg, U256};
+    use std::cell::Cell;
+
+    /// Inspector counting the calls of all the EVMs it is shared with.
+    #[derive(Debug, Default)]
+    struct CallCounter(Cell<usize>);
+
+    impl<DB: Database> Inspector<DB> for &CallCounter {
+        fn call(
+            &mut self,
+            _context: &mut EvmContext<DB>,
+            _inputs: &mut CallInputs,
+        ) -> Option<CallOutcome> {
+            self.0.set(self.0.get() + 1);
+            None
+        }
+    }
+
+    fn noop_register<EXT, DB: Database>(_handler: &mut EvmHandler<'_, EXT, DB>) {}
+
+    #[test]
+    fn applies_handler_registers() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN),
+            Default::default(),
+            Default::default(),
+        );
+
+        let evm = RethEvmBuilder::new(EmptyDB::default(), ())
+            .with_env(env.clone())
+            .with_handler_register(noop_register)
+            .build();
+        assert_eq!(evm.spec_id(), SpecId::CANCUN);
+        assert_eq!(evm.handler.registers.len(), 1);
+
+        let evm = RethEvmBuilder::new(EmptyDB::default(), NoOpInspector)
+            .with_env(env)
+            .with_handler_register(noop_register)
+            .build_with_inspector();
+        assert_eq!(evm.spec_id(), SpecId::CANCUN);
+        assert_eq!(evm.handler.registers.len(), 2);
+    }
+
+    #[test]
+    fn default_builder() {
+        let evm = RethEvmBuilder::<EmptyDB, ()>::default().with_spec_id(SpecId::CANCUN).build();
+        assert_eq!(evm.spec_id(), SpecId::CANCUN);
+        assert!(evm.handler.registers.is_empty());
+    }
+
+    #[test]
+    fn round_trips_env() {
+        let mut env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN),
+            Default::default(),
+            Default::default(),
+        );
+        env.cfg.chain_id = 10;
+        env.block.number = U256::from(42);
+
+        let evm = RethEvmBuilder::new(EmptyDB::default(), ()).with_env(env.clone()).build();
+        let (_, built) = evm.into_db_and_env_with_handler_cfg();
+        assert_eq!(built.spec_id(), SpecId::CANCUN);
+        assert_eq!(built.env, env.env);
+    }
+
+    #[test]
+    fn spec_id_overrides_env() {
+        let evm =
+            RethEvmBuilder::new(EmptyDB::default(), ()).with_spec_id(SpecId::SHANGHAI).build();
+        assert_eq!(evm.spec_id(), SpecId::SHANGHAI);
+
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN),
+            Default::default(),
+            Default::default(),
+        );
+        let evm = RethEvmBuilder::new(EmptyDB::default(), ())
+            .with_env(env)
+            .with_spec_id(SpecId::SHANGHAI)
+            .build();
+        assert_eq!(evm.spec_id(), SpecId::SHANGHAI);
+    }
+
+    #[test]
+    fn shares_external_context_ref() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN),
+            Default::default(),
+            Default::default(),
+        );
+        let counter = CallCounter::default();
+
+        for _ in 0..2 {
+            let mut evm = RethEvmBuilder::with_external_context_ref(EmptyDB::default(), &counter)
+                .with_env(env.clone())
+                .build_with_inspector();
+            evm.transact().unwrap();
+        }
+        assert_eq!(counter.0.get(), 2);
+    }
+}
@@ -0,0 +1,52 @@
+//! Abstraction over the chain specification used to configure the EVM.
+
+use reth_chainspec::{BaseFeeParams, Chain, ChainHardforks, ChainSpec};
+use reth_primitives::B256;
+
+/// The chain specific information that is required to configure the EVM environment.
+///
+/// This allows L2 chains to configure the EVM with their own chain specification type, instead of
+/// wrapping a [`ChainSpec`]. Mapping the active hardforks to a
+/// [`SpecId`](revm_primitives::SpecId) is chain specific and therefore left to the
+/// [`ConfigureEvmEnv`](crate::ConfigureEvmEnv) implementation.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ChainSpecLike: Send + Sync {
+    /// Returns the chain of the specification.
+    fn chain(&self) -> Chain;
+
+    /// Returns the hash of the genesis block.
+    fn genesis_hash(&self) -> B256;
+
+    /// Returns the hardforks of the chain with their activation conditions.
+    fn hardforks(&self) -> &ChainHardforks;
+
+    /// Returns the EIP-1559 base fee parameters active at the given timestamp.
+    fn base_fee_params_at_timestamp(&self, timestamp: u64) -> BaseFeeParams;
+
+    /// Returns `true` if this is an Optimism chain.
+    fn is_optimism(&self) -> bool {
+        self.chain().is_optimism()
+    }
+}
+
+impl ChainSpecLike for ChainSpec {
+    fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    fn genesis_hash(&self) -> B256 {
+        Self::genesis_hash(self)
+    }
+
+    fn hardforks(&self) -> &ChainHardforks {
+        &self.hardforks
+    }
+
+    fn base_fee_params_at_timestamp(&self, timestamp: u64) -> BaseFeeParams {
+        Self::base_fee_params_at_timestamp(self, timestamp)
+    }
+
+    fn is_optimism(&self) -> bool {
+        Self::is_optimism(self)
+    }
+}
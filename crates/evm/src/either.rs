@@ -3,13 +3,15 @@
 use std::fmt::Display;
 
 use crate::execute::{
-    BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
+    BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutor, BlockExecutorProvider,
+    Executor,
 };
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
+use revm::db::State;
 use revm_primitives::db::Database;
 
 // re-export Either
@@ -75,6 +77,58 @@ where
     }
 }
 
+impl<A, B, DB> BlockExecutor<DB> for Either<A, B>
+where
+    A: for<'a> BlockExecutor<
+        DB,
+        Input<'a> = BlockExecutionInput<'a, BlockWithSenders>,
+        Output = BlockExecutionOutput<Receipt>,
+        Error = BlockExecutionError,
+    >,
+    B: for<'a> BlockExecutor<
+        DB,
+        Input<'a> = BlockExecutionInput<'a, BlockWithSenders>,
+        Output = BlockExecutionOutput<Receipt>,
+        Error = BlockExecutionError,
+    >,
+    DB: Database<Error: Into<ProviderError> + Display>,
+{
+    fn execute_block(&mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
+        match self {
+            Self::Left(a) => a.execute_block(input),
+            Self::Right(b) => b.execute_block(input),
+        }
+    }
+
+    fn gas_used(&self) -> u64 {
+        match self {
+            Self::Left(a) => a.gas_used(),
+            Self::Right(b) => b.gas_used(),
+        }
+    }
+
+    fn enable_transaction_snapshots(&mut self) {
+        match self {
+            Self::Left(a) => a.enable_transaction_snapshots(),
+            Self::Right(b) => b.enable_transaction_snapshots(),
+        }
+    }
+
+    fn revert_to(&mut self, tx_index: usize) -> Result<(), BlockExecutionError> {
+        match self {
+            Self::Left(a) => a.revert_to(tx_index),
+            Self::Right(b) => b.revert_to(tx_index),
+        }
+    }
+
+    fn into_state(self) -> State<DB> {
+        match self {
+            Self::Left(a) => a.into_state(),
+            Self::Right(b) => b.into_state(),
+        }
+    }
+}
+
 impl<A, B, DB> BatchExecutor<DB> for Either<A, B>
 where
     A: for<'a> BatchExecutor<
@@ -123,3 +177,6 @@ where
         }
     }
 }
+
+// `ConfigureEvmEnv` is not implemented for `Either`: `fill_cfg_env` has no receiver, so it cannot
+// dispatch on the active variant.
@@ -0,0 +1,46 @@
+//! Object-safe abstraction over a configured EVM.
+
+use revm::{Database, Evm};
+use revm_primitives::{EVMResult, SpecId};
+
+/// An object-safe subset of the [`Evm`] API, so that an EVM can be passed around as a trait object
+/// where the external context and database types are not nameable, e.g. across crate boundaries.
+///
+/// See [`ConfigureEvm::evm_with_db_boxed`](crate::ConfigureEvm::evm_with_db_boxed).
+pub trait EvmLike {
+    /// The error of the underlying database.
+    type Error;
+
+    /// Executes the transaction of the environment, without committing the resulting state.
+    fn transact(&mut self) -> EVMResult<Self::Error>;
+
+    /// Modifies the spec id of the EVM, rebuilding the handler if it changed.
+    fn modify_spec_id(&mut self, spec_id: SpecId);
+}
+
+impl<EXT, DB: Database> EvmLike for Evm<'_, EXT, DB> {
+    type Error = DB::Error;
+
+    fn transact(&mut self) -> EVMResult<Self::Error> {
+        Evm::transact(self)
+    }
+
+    fn modify_spec_id(&mut self, spec_id: SpecId) {
+        Evm::modify_spec_id(self, spec_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use revm::db::EmptyDB;
+
+    #[test]
+    fn boxed_evm_transacts() {
+        let evm = Evm::builder().with_db(EmptyDB::default()).build();
+        let mut evm: Box<dyn EvmLike<Error = Infallible>> = Box::new(evm);
+        evm.modify_spec_id(SpecId::CANCUN);
+        assert!(evm.transact().unwrap().result.is_success());
+    }
+}
@@ -0,0 +1,113 @@
+//! Single-step block execution for debugging.
+
+use crate::ConfigureEvm;
+use reth_chainspec::ChainSpec;
+use reth_execution_errors::{BlockExecutionError, BlockValidationError};
+use reth_primitives::{SealedBlockWithSenders, U256};
+use reth_storage_errors::provider::ProviderError;
+use revm::db::CacheDB;
+use revm_primitives::{
+    db::{DatabaseCommit, DatabaseRef},
+    hash_map::Entry,
+    BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, EvmState, ResultAndState,
+};
+use std::{fmt::Display, sync::Arc};
+
+/// Replays the transactions of a block one by one on top of a database, to inspect the state the
+/// block was in before a given transaction.
+///
+/// The caller can then step through the target transaction on its own, e.g. with a custom
+/// inspector.
+///
+/// # Note
+///
+/// Only the transactions of the block are replayed, pre-block system calls (e.g. EIP-4788) are
+/// not applied.
+#[derive(Debug)]
+pub struct DebugBlock<C, DB> {
+    /// The EVM configuration used to execute the transactions.
+    evm_config: C,
+    /// The chain spec used to configure the EVM environment.
+    chain_spec: Arc<ChainSpec>,
+    /// The state of the block's parent.
+    db: DB,
+}
+
+impl<C, DB> DebugBlock<C, DB>
+where
+    C: ConfigureEvm,
+    DB: DatabaseRef<Error: Into<ProviderError> + Display>,
+{
+    /// Creates a new debugger on top of the given database, which must hold the state of the
+    /// parent of the blocks to debug.
+    pub const fn new(evm_config: C, chain_spec: Arc<ChainSpec>, db: DB) -> Self {
+        Self { evm_config, chain_spec, db }
+    }
+
+    /// Executes all transactions of the block up to, but not including, `tx_index` with the given
+    /// total difficulty, and returns the accumulated state changes.
+    ///
+    /// Returns an error if `tx_index` is out of bounds or if any of the executed transactions
+    /// fails.
+    pub fn at_transaction(
+        &self,
+        block: &SealedBlockWithSenders,
+        total_difficulty: U256,
+        tx_index: usize,
+    ) -> Result<EvmState, BlockExecutionError> {
+        if tx_index > block.body.len() {
+            return Err(BlockExecutionError::msg(format!(
+                "transaction index {tx_index} out of bounds for block with {} transactions",
+                block.body.len()
+            )))
+        }
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        let mut block_env = BlockEnv::default();
+        C::fill_cfg_and_block_env(
+            &mut cfg,
+            &mut block_env,
+            &self.chain_spec,
+            &block.header,
+            Some(total_difficulty),
+        );
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default());
+
+        let mut evm = self.evm_config.evm_with_env(CacheDB::new(&self.db), env);
+        let mut accumulated = EvmState::default();
+        for (sender, transaction) in block.transactions_with_sender().take(tx_index) {
+            self.evm_config.fill_tx_env(evm.tx_mut(), transaction, *sender);
+
+            let ResultAndState { state, .. } = evm.transact().map_err(move |err| {
+                let new_err = match err {
+                    EVMError::Transaction(e) => EVMError::Transaction(e),
+                    EVMError::Header(e) => EVMError::Header(e),
+                    EVMError::Database(e) => EVMError::Database(e.into()),
+                    EVMError::Custom(e) => EVMError::Custom(e),
+                    EVMError::Precompile(e) => EVMError::Precompile(e),
+                };
+                BlockValidationError::EVM {
+                    hash: transaction.recalculate_hash(),
+                    error: Box::new(new_err),
+                }
+            })?;
+            evm.db_mut().commit(state.clone());
+
+            for (address, account) in state {
+                match accumulated.entry(address) {
+                    Entry::Occupied(mut entry) => {
+                        let existing = entry.get_mut();
+                        existing.info = account.info;
+                        existing.status |= account.status;
+                        existing.storage.extend(account.storage);
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(account);
+                    }
+                }
+            }
+        }
+
+        Ok(accumulated)
+    }
+}
@@ -0,0 +1,347 @@
+//! EIP-1559 fee burning and distribution.
+
+use reth_chainspec::{ChainSpec, EthereumHardfork};
+use reth_execution_errors::BlockExecutionError;
+use reth_primitives::{Address, Receipt, SealedBlock, U256};
+use reth_storage_errors::provider::ProviderError;
+use revm::db::{AccountStatus, BundleState, TransitionAccount};
+use revm_primitives::{db::Database, hash_map};
+
+/// Credits the fees paid by the transactions of a block to the accounts of a [`BundleState`].
+///
+/// The priority fees are credited to the block beneficiary. The burned fees are destroyed, unless
+/// a treasury is configured, e.g. on test chains, in which case they are credited to it.
+///
+/// The Ethereum and Optimism executors already credit the priority fees to the beneficiary while
+/// executing the transactions, so this is meant for bundles that were built without crediting the
+/// fees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeDistributor {
+    /// The account credited with the burned fees, if any.
+    treasury: Option<Address>,
+}
+
+impl FeeDistributor {
+    /// Creates a new distributor that destroys the burned fees.
+    pub const fn new() -> Self {
+        Self { treasury: None }
+    }
+
+    /// Credits the burned fees to the given treasury instead of destroying them.
+    pub const fn with_treasury(mut self, treasury: Address) -> Self {
+        self.treasury = Some(treasury);
+        self
+    }
+
+    /// Returns the account credited with the burned fees, if any.
+    pub const fn treasury(&self) -> Option<Address> {
+        self.treasury
+    }
+
+    /// Computes the fees of the block with [`FeeDistribution::compute`] and credits them to the
+    /// accounts of the bundle.
+    ///
+    /// The bundle is expected to contain the execution of the block, its reverts being the last
+    /// ones of the bundle. The previous state of a credited account is recorded there if the block
+    /// did not change the account already, so that reverting the block also reverts the fees.
+    /// Accounts that are not part of the bundle are loaded from the database.
+    pub fn apply<DB>(
+        &self,
+        db: &mut DB,
+        state: &mut BundleState,
+        block: &SealedBlock,
+        receipts: &[Receipt],
+        chain_spec: &ChainSpec,
+    ) -> Result<FeeDistribution, BlockExecutionError>
+    where
+        DB: Database<Error: Into<ProviderError>>,
+    {
+        let distribution = FeeDistribution::compute(block, receipts, chain_spec)?;
+
+        increment_balance(db, state, block.beneficiary, distribution.tips)?;
+        if let Some(treasury) = self.treasury {
+            increment_balance(db, state, treasury, distribution.burned)?;
+        }
+
+        Ok(distribution)
+    }
+}
+
+/// Increments the balance of the account in the bundle, and records its previous state in the
+/// reverts of the last block if they do not contain the account yet.
+fn increment_balance<DB>(
+    db: &mut DB,
+    state: &mut BundleState,
+    address: Address,
+    amount: U256,
+) -> Result<(), BlockExecutionError>
+where
+    DB: Database<Error: Into<ProviderError>>,
+{
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    let (previous_info, previous_status) = match state.state.get(&address) {
+        Some(account) => (account.info.clone(), account.status),
+        None => {
+            let info = db.basic(address).map_err(Into::<ProviderError>::into)?;
+            let status = if info.is_some() {
+                AccountStatus::Loaded
+            } else {
+                AccountStatus::LoadedNotExisting
+            };
+            (info, status)
+        }
+    };
+
+    let mut info = previous_info.clone().unwrap_or_default();
+    info.balance = info.balance.saturating_add(amount);
+    let transition = TransitionAccount {
+        status: previous_status.on_changed(info.has_no_code_and_nonce()),
+        info: Some(info),
+        previous_info,
+        previous_status,
+        storage: Default::default(),
+        storage_was_destroyed: false,
+    };
+
+    let revert = match state.state.entry(address) {
+        hash_map::Entry::Occupied(mut entry) => {
+            let account = entry.get_mut();
+            state.state_size -= account.size_hint();
+            let revert = account.update_and_create_revert(transition);
+            state.state_size += account.size_hint();
+            revert
+        }
+        hash_map::Entry::Vacant(entry) => {
+            let account = transition.present_bundle_account();
+            let revert = transition.create_revert();
+            state.state_size += account.size_hint();
+            entry.insert(account);
+            revert
+        }
+    };
+
+    if let (Some(revert), Some(reverts)) = (revert, state.reverts.last_mut()) {
+        // a revert of the account in this block already restores its state before the block
+        if !reverts.iter().any(|(reverted, _)| *reverted == address) {
+            state.reverts_size += revert.size_hint();
+            reverts.push((address, revert));
+        }
+    }
+
+    Ok(())
+}
+
+/// The fees paid by the transactions of a block, split into their burned and tipped parts.
+///
+/// This is only the accounting of the fees, see [`FeeDistributor`] to credit them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeDistribution {
+    /// The base fee and blob fee portion of the fees, `base_fee * gas_used` and
+    /// `blob_gas_price * blob_gas_used`, which is destroyed.
+    pub burned: U256,
+    /// The priority fee portion of the fees, credited to the block beneficiary.
+    pub tips: U256,
+}
+
+impl FeeDistribution {
+    /// Computes the burned and tipped fees of the block from its receipts in a single pass.
+    ///
+    /// Returns an error if the cumulative gas used of the receipts is not increasing.
+    pub fn compute(
+        block: &SealedBlock,
+        receipts: &[Receipt],
+        chain_spec: &ChainSpec,
+    ) -> Result<Self, BlockExecutionError> {
+        let base_fee = chain_spec
+            .fork(EthereumHardfork::London)
+            .active_at_block(block.number)
+            .then_some(block.base_fee_per_gas)
+            .flatten();
+        let blob_gas_price = U256::from(block.blob_fee().unwrap_or_default());
+
+        let mut distribution = Self::default();
+        let mut cumulative_gas_used = 0;
+        for (tx, receipt) in block.body.iter().zip(receipts) {
+            let gas_used =
+                receipt.cumulative_gas_used.checked_sub(cumulative_gas_used).ok_or_else(|| {
+                    BlockExecutionError::msg(format!(
+                        "cumulative gas used of transaction {} decreased from {} to {}",
+                        tx.hash, cumulative_gas_used, receipt.cumulative_gas_used
+                    ))
+                })?;
+            let gas_used = U256::from(gas_used);
+            cumulative_gas_used = receipt.cumulative_gas_used;
+
+            let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            distribution.tips += U256::from(tip) * gas_used;
+            distribution.burned += U256::from(base_fee.unwrap_or_default()) * gas_used;
+            distribution.burned +=
+                blob_gas_price * U256::from(tx.blob_gas_used().unwrap_or_default());
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the total fees paid by the transactions of the block.
+    pub fn total(&self) -> U256 {
+        self.burned + self.tips
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_primitives::{
+        constants::eip4844::DATA_GAS_PER_BLOB, Header, Signature, Transaction, TransactionSigned,
+        TxEip1559, TxEip4844, TxKind, TxLegacy, TxType, B256,
+    };
+    use revm::db::{states::reverts::AccountInfoRevert, EmptyDBTyped};
+    use revm_primitives::{AccountInfo, HashMap};
+
+    fn block_and_receipts(base_fee: u64) -> (SealedBlock, Vec<Receipt>) {
+        let transactions = vec![
+            Transaction::Legacy(TxLegacy {
+                gas_price: 30,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(3)),
+                ..Default::default()
+            }),
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 40,
+                max_priority_fee_per_gas: 3,
+                gas_limit: 50_000,
+                to: TxKind::Call(Address::with_last_byte(4)),
+                ..Default::default()
+            }),
+        ];
+        let body = transactions
+            .into_iter()
+            .map(|tx| TransactionSigned::from_transaction_and_signature(tx, Signature::default()))
+            .collect();
+        let receipts = vec![
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21_000,
+                ..Default::default()
+            },
+            Receipt {
+                tx_type: TxType::Eip1559,
+                success: true,
+                cumulative_gas_used: 60_000,
+                ..Default::default()
+            },
+        ];
+        let header = Header {
+            // london activation block on mainnet
+            number: 12_965_000,
+            beneficiary: Address::with_last_byte(1),
+            base_fee_per_gas: Some(base_fee),
+            ..Default::default()
+        };
+        let block = SealedBlock { header: header.seal_slow(), body, ..Default::default() };
+        (block, receipts)
+    }
+
+    #[test]
+    fn fee_accounting_identity() {
+        let base_fee = 20;
+        let (block, receipts) = block_and_receipts(base_fee);
+
+        let distribution = FeeDistribution::compute(&block, &receipts, &MAINNET).unwrap();
+
+        // legacy: 21_000 gas at 30, eip1559: 39_000 gas at min(40, 20 + 3) = 23
+        let total_paid = U256::from(21_000u64 * 30 + 39_000 * 23);
+        assert_eq!(distribution.total(), total_paid);
+        assert_eq!(distribution.burned, U256::from(60_000 * base_fee));
+        assert_eq!(distribution.tips, U256::from(21_000u64 * 10 + 39_000 * 3));
+    }
+
+    #[test]
+    fn decreasing_cumulative_gas_used() {
+        let (block, mut receipts) = block_and_receipts(20);
+        receipts[1].cumulative_gas_used = 20_000;
+
+        assert!(FeeDistribution::compute(&block, &receipts, &MAINNET).is_err());
+    }
+
+    #[test]
+    fn blob_fees_are_burned() {
+        let (mut block, mut receipts) = block_and_receipts(20);
+        let blob_tx = Transaction::Eip4844(TxEip4844 {
+            max_fee_per_gas: 20,
+            max_fee_per_blob_gas: 1,
+            gas_limit: 21_000,
+            blob_versioned_hashes: vec![B256::ZERO, B256::ZERO],
+            ..Default::default()
+        });
+        block
+            .body
+            .push(TransactionSigned::from_transaction_and_signature(blob_tx, Signature::default()));
+        receipts.push(Receipt {
+            tx_type: TxType::Eip4844,
+            success: true,
+            cumulative_gas_used: 81_000,
+            ..Default::default()
+        });
+        let mut header = block.header.clone().unseal();
+        // a blob gas price of 1
+        header.excess_blob_gas = Some(0);
+        block.header = header.seal_slow();
+
+        let distribution = FeeDistribution::compute(&block, &receipts, &MAINNET).unwrap();
+
+        assert_eq!(distribution.burned, U256::from(81_000 * 20 + 2 * DATA_GAS_PER_BLOB));
+    }
+
+    #[test]
+    fn apply_credits_fees_with_reverts() {
+        let (block, receipts) = block_and_receipts(20);
+        let beneficiary = block.beneficiary;
+        let treasury = Address::with_last_byte(2);
+        let original = AccountInfo { balance: U256::from(1), ..Default::default() };
+        let present = AccountInfo { balance: U256::from(5), ..Default::default() };
+        // the block already changed the beneficiary
+        let mut state = BundleState::new(
+            [(beneficiary, Some(original.clone()), Some(present.clone()), HashMap::new())],
+            [vec![(beneficiary, Some(Some(original.clone())), vec![])]],
+            [],
+        );
+
+        let distribution = FeeDistributor::new()
+            .with_treasury(treasury)
+            .apply(
+                &mut EmptyDBTyped::<ProviderError>::new(),
+                &mut state,
+                &block,
+                &receipts,
+                &MAINNET,
+            )
+            .unwrap();
+
+        let balance = |state: &BundleState, address| {
+            state
+                .account(&address)
+                .and_then(|account| account.info.as_ref())
+                .map(|info| info.balance)
+        };
+        assert_eq!(balance(&state, beneficiary), Some(present.balance + distribution.tips));
+        assert_eq!(balance(&state, treasury), Some(distribution.burned));
+
+        // the treasury did not exist before the block, the beneficiary revert is kept
+        let reverts = state.reverts.last().unwrap();
+        assert_eq!(reverts.len(), 2);
+        let (_, revert) = reverts.iter().find(|(address, _)| *address == treasury).unwrap();
+        assert_eq!(revert.account, AccountInfoRevert::DeleteIt);
+
+        assert!(state.revert_latest());
+        assert_eq!(balance(&state, beneficiary), Some(original.balance));
+        assert!(state.account(&treasury).is_none());
+        assert_eq!(state.reverts_size, 0);
+        assert_eq!(state.state_size, state.account(&beneficiary).unwrap().size_hint());
+    }
+}
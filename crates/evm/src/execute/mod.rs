@@ -1,10 +1,10 @@
 //! Traits for execution.
 
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Receipt, Request, U256};
+use reth_primitives::{BlockNumber, BlockWithSenders, Receipt, Request, B256, U256};
 use reth_prune_types::PruneModes;
-use revm::db::BundleState;
-use revm_primitives::db::Database;
+use revm::db::{BundleState, State};
+use revm_primitives::{db::Database, TxEnv};
 use std::fmt::Display;
 
 #[cfg(not(feature = "std"))]
@@ -13,6 +13,18 @@ use alloc::vec::Vec;
 pub use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 pub use reth_storage_errors::provider::ProviderError;
 
+mod debug_block;
+pub use debug_block::DebugBlock;
+
+mod fee_distributor;
+pub use fee_distributor::{FeeDistribution, FeeDistributor};
+
+mod snapshot_db;
+pub use snapshot_db::SnapshotDatabase;
+
+mod tx_snapshots;
+pub use tx_snapshots::TransactionSnapshots;
+
 /// A general purpose executor trait that executes an input (e.g. block) and produces an output
 /// (e.g. state changes and receipts).
 ///
@@ -36,6 +48,42 @@ pub trait Executor<DB> {
     fn execute(self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error>;
 }
 
+/// An [`Executor`] that executes a single block on top of a revm [`State`].
+pub trait BlockExecutor<DB>: Executor<DB> {
+    /// Executes the block like [`Executor::execute`], without consuming the executor.
+    ///
+    /// If execution fails, the state changes of the transactions executed so far remain in the
+    /// [`State`].
+    fn execute_block(&mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error>;
+
+    /// Returns the cumulative gas used by the transactions of the current block that were executed
+    /// so far.
+    ///
+    /// After a failed [`BlockExecutor::execute_block`], this is the gas used by the transactions
+    /// before the failing one.
+    fn gas_used(&self) -> u64;
+
+    /// Enables taking a snapshot of the [`State`] before each transaction, which is required by
+    /// [`BlockExecutor::revert_to`].
+    ///
+    /// Snapshots clone the cached state, so they are disabled by default.
+    fn enable_transaction_snapshots(&mut self);
+
+    /// Rolls the [`State`] back to the snapshot taken before the transaction at `tx_index` of the
+    /// block that is executed, or whose execution failed.
+    ///
+    /// Returns an error if no snapshot was taken before that transaction, e.g. because snapshots
+    /// are not enabled or the block execution completed.
+    fn revert_to(&mut self, tx_index: usize) -> Result<(), BlockExecutionError>;
+
+    /// Consumes the executor and returns the [`State`] that wraps the underlying database,
+    /// including all state changes that have not been taken yet.
+    ///
+    /// This hands the state over without cloning the [`BundleState`], e.g. to compute the state
+    /// root right away.
+    fn into_state(self) -> State<DB>;
+}
+
 /// A general purpose executor that can execute multiple inputs in sequence, validate the outputs,
 /// and keep track of the state over the entire batch.
 pub trait BatchExecutor<DB> {
@@ -93,7 +141,8 @@ pub trait BatchExecutor<DB> {
 
 /// The output of an ethereum block.
 ///
-/// Contains the state changes, transaction receipts, and total gas used in the block.
+/// Contains the state changes, transaction receipts, EIP-7685 requests, and total gas and blob gas
+/// used in the block.
 ///
 /// TODO(mattsse): combine with `ExecutionOutcome`
 #[derive(Debug)]
@@ -106,21 +155,72 @@ pub struct BlockExecutionOutput<T> {
     pub requests: Vec<Request>,
     /// The total gas used by the block.
     pub gas_used: u64,
+    /// The total blob gas used by the block, `None` before Cancun.
+    pub blob_gas_used: Option<u64>,
+}
+
+impl BlockExecutionOutput<Receipt> {
+    /// Returns the cumulative gas used in the block after the transaction at the given index.
+    ///
+    /// Returns `None` if there is no transaction at the given index.
+    pub fn cumulative_gas_used(&self, tx_index: usize) -> Option<u64> {
+        self.receipts.get(tx_index).map(|receipt| receipt.cumulative_gas_used)
+    }
+
+    /// Returns the gas used by the transaction at the given index.
+    ///
+    /// Returns `None` if there is no transaction at the given index, or if the cumulative gas used
+    /// of the receipts decreases at that index.
+    pub fn transaction_gas_used(&self, tx_index: usize) -> Option<u64> {
+        let cumulative_gas_used = self.cumulative_gas_used(tx_index)?;
+        let previous = tx_index.checked_sub(1).and_then(|i| self.cumulative_gas_used(i));
+        cumulative_gas_used.checked_sub(previous.unwrap_or_default())
+    }
+}
+
+/// Returns the gas price actually paid by the transaction of the given [`TxEnv`] at the given
+/// base fee.
+///
+/// This is `min(max_fee, base_fee + priority_fee)` for EIP-1559 style transactions and the gas
+/// price for legacy transactions, which lets receipt builders compute the fee of an executed
+/// transaction without the original transaction.
+pub fn effective_gas_price(tx_env: &TxEnv, base_fee: u64) -> u128 {
+    let max_fee = tx_env.gas_price.saturating_to::<u128>();
+    match tx_env.gas_priority_fee {
+        Some(priority_fee) => {
+            max_fee.min((base_fee as u128).saturating_add(priority_fee.saturating_to()))
+        }
+        None => max_fee,
+    }
 }
 
-/// A helper type for ethereum block inputs that consists of a block and the total difficulty.
+/// A helper type for ethereum block inputs that aggregates all per-block inputs: the block, its
+/// total difficulty and the parent beacon block root.
 #[derive(Debug)]
 pub struct BlockExecutionInput<'a, Block> {
     /// The block to execute.
     pub block: &'a Block,
     /// The total difficulty of the block.
     pub total_difficulty: U256,
+    /// The parent beacon block root to use for the EIP-4788 pre-block call.
+    ///
+    /// If `None`, the root of the block's header is used.
+    pub parent_beacon_block_root: Option<B256>,
 }
 
 impl<'a, Block> BlockExecutionInput<'a, Block> {
     /// Creates a new input.
     pub const fn new(block: &'a Block, total_difficulty: U256) -> Self {
-        Self { block, total_difficulty }
+        Self { block, total_difficulty, parent_beacon_block_root: None }
+    }
+
+    /// Sets the parent beacon block root, overriding the root of the block's header.
+    pub const fn with_parent_beacon_block_root(
+        mut self,
+        parent_beacon_block_root: Option<B256>,
+    ) -> Self {
+        self.parent_beacon_block_root = parent_beacon_block_root;
+        self
     }
 }
 
@@ -240,6 +340,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_output_gas_used() {
+        let receipt = |cumulative_gas_used| Receipt {
+            cumulative_gas_used,
+            success: true,
+            ..Default::default()
+        };
+        let output = BlockExecutionOutput {
+            state: Default::default(),
+            receipts: vec![receipt(21_000), receipt(71_000)],
+            requests: vec![],
+            gas_used: 71_000,
+            blob_gas_used: None,
+        };
+
+        assert_eq!(output.cumulative_gas_used(1), Some(71_000));
+        assert_eq!(output.transaction_gas_used(0), Some(21_000));
+        assert_eq!(output.transaction_gas_used(1), Some(50_000));
+        assert_eq!(output.transaction_gas_used(2), None);
+
+        // malformed receipts with a decreasing cumulative gas used
+        let output =
+            BlockExecutionOutput { receipts: vec![receipt(71_000), receipt(21_000)], ..output };
+        assert_eq!(output.transaction_gas_used(1), None);
+    }
+
     #[test]
     fn test_provider() {
         let provider = TestExecutorProvider;
@@ -255,4 +381,21 @@ mod tests {
         let block = BlockWithSenders::new(block, Default::default()).unwrap();
         let _ = executor.execute(BlockExecutionInput::new(&block, U256::ZERO));
     }
+
+    #[test]
+    fn effective_gas_price_is_capped() {
+        let legacy = TxEnv { gas_price: U256::from(30), ..Default::default() };
+        assert_eq!(effective_gas_price(&legacy, 20), 30);
+
+        let mut eip1559 = TxEnv {
+            gas_price: U256::from(40),
+            gas_priority_fee: Some(U256::from(3)),
+            ..Default::default()
+        };
+        assert_eq!(effective_gas_price(&eip1559, 20), 23);
+
+        // the priority fee is capped by the max fee
+        eip1559.gas_priority_fee = Some(U256::from(30));
+        assert_eq!(effective_gas_price(&eip1559, 20), 40);
+    }
 }
@@ -0,0 +1,138 @@
+//! A [`Database`] wrapper that keeps state changes in memory.
+//!
+//! The wrapper reads from reth's [`StateProvider`], which is why `reth-evm` depends on
+//! `reth-storage-api`.
+
+use reth_primitives::{Account, Address, StorageKey, B256, KECCAK_EMPTY, U256};
+use reth_storage_api::StateProvider;
+use reth_storage_errors::provider::ProviderError;
+use revm::db::BundleState;
+use revm_primitives::{db::Database, AccountInfo, Bytecode};
+use std::collections::{HashMap, HashSet};
+
+/// A [`Database`] that reads from a [`StateProvider`] and keeps all committed state changes in
+/// memory, without ever writing them to the underlying provider.
+///
+/// This is useful for `eth_call` and bundle simulation where the state of previous transactions
+/// must be visible to the next ones, but must be discarded afterwards.
+///
+/// Compared to a plain state cache, this differs in a few ways:
+///  - [`commit`](Self::commit) takes `&mut self`, because the [`Database`] reads already require
+///    exclusive access and interior mutability would only hide that.
+///  - Bytecodes are kept as [`Bytecode`] rather than raw bytes, so that the kind of the bytecode
+///    (e.g. EOF) is preserved when it is read back.
+///  - Destroyed accounts are tracked separately from the changed accounts, so that they are not
+///    read from the underlying provider again.
+#[derive(Debug)]
+pub struct SnapshotDatabase<DB> {
+    /// The underlying state provider.
+    inner: DB,
+    /// Accounts changed by committed state.
+    cache: HashMap<Address, Account>,
+    /// Storage slots changed by committed state, by account.
+    storage: HashMap<Address, HashMap<StorageKey, U256>>,
+    /// Accounts destroyed by committed state, their storage is wiped.
+    ///
+    /// An account that was destroyed and re-created afterwards is part of the cache.
+    wiped: HashSet<Address>,
+    /// Bytecodes introduced by committed state.
+    code: HashMap<B256, Bytecode>,
+}
+
+impl<DB: StateProvider> SnapshotDatabase<DB> {
+    /// Creates a new snapshot database on top of the given state provider.
+    pub fn new(inner: DB) -> Self {
+        Self {
+            inner,
+            cache: HashMap::new(),
+            storage: HashMap::new(),
+            wiped: HashSet::new(),
+            code: HashMap::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying state provider.
+    pub const fn inner(&self) -> &DB {
+        &self.inner
+    }
+
+    /// Consumes the type and returns the underlying state provider.
+    pub fn into_inner(self) -> DB {
+        self.inner
+    }
+
+    /// Applies the given state changes on top of the already committed ones.
+    pub fn commit(&mut self, state: &BundleState) {
+        for (address, account) in &state.state {
+            let info = account.info.clone();
+            if account.was_destroyed() || info.is_none() {
+                self.wiped.insert(*address);
+                self.storage.remove(address);
+            }
+
+            match info {
+                Some(info) => self.cache.insert(*address, info.into()),
+                None => self.cache.remove(address),
+            };
+
+            if !account.storage.is_empty() {
+                let storage = self.storage.entry(*address).or_default();
+                for (slot, value) in &account.storage {
+                    storage.insert(B256::new(slot.to_be_bytes()), value.present_value);
+                }
+            }
+        }
+
+        self.code.extend(state.contracts.iter().map(|(hash, code)| (*hash, code.clone())));
+    }
+
+    /// Discards all committed state changes.
+    pub fn reset(&mut self) {
+        self.cache.clear();
+        self.storage.clear();
+        self.wiped.clear();
+        self.code.clear();
+    }
+}
+
+impl<DB: StateProvider> Database for SnapshotDatabase<DB> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let account = match self.cache.get(&address) {
+            Some(account) => Some(*account),
+            None if self.wiped.contains(&address) => None,
+            None => self.inner.basic_account(address)?,
+        };
+        Ok(account.map(|account| AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+            code: None,
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.code.get(&code_hash) {
+            return Ok(code.clone())
+        }
+        Ok(self.inner.bytecode_by_hash(code_hash)?.unwrap_or_default().0)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let key = B256::new(index.to_be_bytes());
+        if let Some(value) = self.storage.get(&address).and_then(|storage| storage.get(&key)) {
+            return Ok(*value)
+        }
+        if self.wiped.contains(&address) {
+            return Ok(U256::ZERO)
+        }
+        Ok(self.inner.storage(address, key)?.unwrap_or_default())
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let block_number =
+            number.try_into().map_err(|_| ProviderError::BlockNumberOverflow(number))?;
+        Ok(self.inner.block_hash(block_number)?.unwrap_or_default())
+    }
+}
@@ -0,0 +1,93 @@
+//! Snapshots of the [`State`] taken before the transactions of a block.
+
+use reth_execution_errors::BlockExecutionError;
+use reth_primitives::B256;
+use revm::db::{CacheState, State, TransitionState};
+use std::collections::BTreeMap;
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec::Vec};
+
+/// The [`State`] before a transaction of the block was executed.
+#[derive(Debug, Clone)]
+struct TransactionSnapshot {
+    /// The cached accounts and storage.
+    cache: CacheState,
+    /// The transitions of the block that were not merged yet.
+    transition_state: Option<TransitionState>,
+    /// The block hashes loaded so far.
+    block_hashes: BTreeMap<u64, B256>,
+    /// The cumulative gas used by the previous transactions of the block.
+    gas_used: u64,
+}
+
+/// Snapshots of the [`State`] taken before each transaction of the block that is executed, used
+/// to roll the state back with [`BlockExecutor::revert_to`](super::BlockExecutor::revert_to).
+///
+/// Taking a snapshot clones the cached state, so snapshots are only taken once enabled.
+#[derive(Debug, Default)]
+pub struct TransactionSnapshots {
+    /// Whether snapshots are taken.
+    enabled: bool,
+    /// The snapshot before each transaction, by transaction index.
+    snapshots: Vec<TransactionSnapshot>,
+}
+
+impl TransactionSnapshots {
+    /// Creates a new set of snapshots, with snapshots disabled.
+    pub const fn new() -> Self {
+        Self { enabled: false, snapshots: Vec::new() }
+    }
+
+    /// Returns `true` if snapshots are taken.
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables taking snapshots.
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Discards all snapshots, e.g. when a new block is executed.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Takes a snapshot of the state before the next transaction of the block, if enabled.
+    pub fn take<DB>(&mut self, state: &State<DB>, gas_used: u64) {
+        if !self.enabled {
+            return
+        }
+        self.snapshots.push(TransactionSnapshot {
+            cache: state.cache.clone(),
+            transition_state: state.transition_state.clone(),
+            block_hashes: state.block_hashes.clone(),
+            gas_used,
+        });
+    }
+
+    /// Rolls the state back to the snapshot taken before the transaction at `tx_index`, and
+    /// discards the snapshots of that transaction and the following ones.
+    ///
+    /// Returns the cumulative gas used by the transactions before `tx_index`, or an error if no
+    /// snapshot was taken before that transaction.
+    pub fn revert_to<DB>(
+        &mut self,
+        state: &mut State<DB>,
+        tx_index: usize,
+    ) -> Result<u64, BlockExecutionError> {
+        if tx_index >= self.snapshots.len() {
+            return Err(BlockExecutionError::msg(format!(
+                "no state snapshot before transaction {tx_index}, {} snapshots were taken",
+                self.snapshots.len()
+            )))
+        }
+
+        let snapshot = self.snapshots.drain(tx_index..).next().expect("index is in bounds");
+        state.cache = snapshot.cache;
+        state.transition_state = snapshot.transition_state;
+        state.block_hashes = snapshot.block_hashes;
+        Ok(snapshot.gas_used)
+    }
+}
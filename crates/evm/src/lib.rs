@@ -13,16 +13,32 @@
 extern crate alloc;
 
 use core::ops::Deref;
+use std::collections::HashMap;
 
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{EthereumHardfork, EthereumHardforks, Head};
+use reth_execution_errors::BlockValidationError;
 use reth_primitives::{
-    revm::env::{fill_block_env, fill_tx_env},
-    Address, Header, TransactionSigned, TransactionSignedEcRecovered, U256,
+    constants::EIP1559_INITIAL_BASE_FEE,
+    revm::env::{fill_block_env_with_coinbase, fill_block_env_with_hardforks, fill_tx_env},
+    Address, GenesisAccount, Header, SealedBlock, Transaction, TransactionSigned,
+    TransactionSignedEcRecovered, U256,
+};
+use reth_storage_errors::provider::ProviderError;
+use revm::{
+    db::WrapDatabaseRef, inspector_handle_register, Database, DatabaseRef, Evm, EvmBuilder,
+    GetInspector,
 };
-use revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId, TxEnv};
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+pub mod builder;
+mod chain_spec;
+pub use chain_spec::ChainSpecLike;
 pub mod either;
+mod evm_like;
+pub use evm_like::EvmLike;
 pub mod execute;
 pub mod noop;
 pub mod provider;
@@ -47,9 +63,26 @@ pub trait ConfigureEvm: ConfigureEvmEnv {
         db: DB,
     ) -> Evm<'a, Self::DefaultExternalContext<'a>, DB>;
 
+    /// Returns a new EVM that reads from the given database reference.
+    ///
+    /// This allows sharing a single [`DatabaseRef`] across many EVM instances without moving it
+    /// into each of them.
+    ///
+    /// Same as [`evm`](ConfigureEvm::evm), this does not configure the EVM environment.
+    fn evm_ref<'a, DB: DatabaseRef + 'a>(
+        &'a self,
+        db: &'a DB,
+    ) -> Evm<'a, Self::DefaultExternalContext<'a>, WrapDatabaseRef<&'a DB>> {
+        self.evm(WrapDatabaseRef(db))
+    }
+
     /// Returns a new EVM with the given database configured with the given environment settings,
     /// including the spec id.
     ///
+    /// The spec id is applied with [`Evm::modify_spec_id`], which also updates the spec id of the
+    /// handler's [`HandlerCfg`](revm_primitives::HandlerCfg), so [`Evm::handler_cfg`] is in sync
+    /// with the environment.
+    ///
     /// This will preserve any handler modifications
     fn evm_with_env<'a, DB: Database + 'a>(
         &'a self,
@@ -62,6 +95,59 @@ pub trait ConfigureEvm: ConfigureEvmEnv {
         evm
     }
 
+    /// Same as [`evm_with_env`](ConfigureEvm::evm_with_env), but reads from a boxed database and
+    /// returns the EVM as an [`EvmLike`] trait object.
+    ///
+    /// This allows using an EVM where its concrete type can not be named, at the cost of dynamic
+    /// dispatch.
+    fn evm_with_db_boxed<'a>(
+        &'a self,
+        db: Box<dyn DatabaseRef<Error = ProviderError> + 'a>,
+        env: EnvWithHandlerCfg,
+    ) -> Box<dyn EvmLike<Error = ProviderError> + 'a>
+    where
+        Self::DefaultExternalContext<'a>: 'a,
+    {
+        Box::new(self.evm_with_env(WrapDatabaseRef(db), env))
+    }
+
+    /// Same as [`evm_with_env`](ConfigureEvm::evm_with_env), but validates that the spec id of the
+    /// environment is not behind the spec id that is active for the environment's block according
+    /// to the given chain spec.
+    ///
+    /// An outdated spec id silently produces incorrect execution results, which is why this
+    /// returns [`BlockValidationError::IncompatibleSpecId`] instead.
+    fn try_evm_with_env<'a, DB: Database + 'a>(
+        &'a self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        chain_spec: &dyn ChainSpecLike,
+    ) -> Result<Evm<'a, Self::DefaultExternalContext<'a>, DB>, BlockValidationError> {
+        let block_number = env.block.number.saturating_to::<u64>();
+        let header = Header {
+            number: block_number,
+            timestamp: env.block.timestamp.saturating_to(),
+            difficulty: env.block.difficulty,
+            ..Default::default()
+        };
+        // the total difficulty is only used to determine whether the merge is active
+        let total_difficulty = env.block.prevrandao.is_none().then_some(U256::ZERO);
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        Self::fill_cfg_env(&mut cfg, chain_spec, &header, total_difficulty);
+
+        let active_spec_id = cfg.handler_cfg.spec_id;
+        if env.spec_id() < active_spec_id {
+            return Err(BlockValidationError::IncompatibleSpecId {
+                spec_id: env.spec_id(),
+                active_spec_id,
+                block_number,
+            })
+        }
+
+        Ok(self.evm_with_env(db, env))
+    }
+
     /// Returns a new EVM with the given database configured with the given environment settings,
     /// including the spec id.
     ///
@@ -100,6 +186,24 @@ pub trait ConfigureEvm: ConfigureEvmEnv {
             .append_handler_register(inspector_handle_register)
             .build()
     }
+
+    /// Consumes the given EVM and returns a new EVM with the given inspector attached.
+    ///
+    /// Unlike [`evm_with_inspector`](ConfigureEvm::evm_with_inspector), this keeps the database,
+    /// the environment and the spec id of the given EVM, so an inspector can be attached to an EVM
+    /// that was already configured, e.g. with [`evm_with_env`](ConfigureEvm::evm_with_env).
+    fn with_inspector<'a, DB, EXT, I>(
+        &'a self,
+        evm: Evm<'a, EXT, DB>,
+        inspector: I,
+    ) -> Evm<'a, I, DB>
+    where
+        DB: Database + 'a,
+        I: GetInspector<DB>,
+    {
+        let (db, env) = evm.into_db_and_env_with_handler_cfg();
+        self.evm_with_env_and_inspector(db, env, inspector)
+    }
 }
 
 /// This represents the set of methods used to configure the EVM's environment before block
@@ -108,37 +212,234 @@ pub trait ConfigureEvm: ConfigureEvmEnv {
 /// Default trait method  implementation is done w.r.t. L1.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
+    /// The error type returned by [`tx_env`](ConfigureEvmEnv::tx_env).
+    ///
+    /// This allows configurations, e.g. wrapping another configuration for an L2 chain, to
+    /// reject a transaction with additional validation. Configurations without any additional
+    /// validation should use [`Infallible`](core::convert::Infallible).
+    type Error: std::error::Error + Send + Sync + 'static;
+
     /// Returns a [`TxEnv`] from a [`TransactionSignedEcRecovered`].
-    fn tx_env(&self, transaction: &TransactionSignedEcRecovered) -> TxEnv {
+    fn tx_env(&self, transaction: &TransactionSignedEcRecovered) -> Result<TxEnv, Self::Error> {
         let mut tx_env = TxEnv::default();
         self.fill_tx_env(&mut tx_env, transaction.deref(), transaction.signer());
+        Ok(tx_env)
+    }
+
+    /// Returns a [`TxEnv`] from an unsigned [`Transaction`] and the given sender address.
+    fn tx_env_unsigned(&self, transaction: &Transaction, sender: Address) -> TxEnv {
+        let mut tx_env = TxEnv::default();
+        self.fill_tx_env_unsigned(&mut tx_env, transaction, sender);
         tx_env
     }
 
     /// Fill transaction environment from a [`TransactionSigned`] and the given sender address.
+    ///
+    /// By default this delegates to [`fill_tx_env_unsigned`](ConfigureEvmEnv::fill_tx_env_unsigned),
+    /// so chain specific overrides only need to be implemented there, unless they depend on the
+    /// signed transaction itself.
     fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
+        self.fill_tx_env_unsigned(tx_env, &transaction.transaction, sender)
+    }
+
+    /// Fill transaction environment from an unsigned [`Transaction`] and the given sender address.
+    ///
+    /// This is useful to simulate transactions that are not signed, e.g. for `eth_call` or gas
+    /// estimation.
+    fn fill_tx_env_unsigned(&self, tx_env: &mut TxEnv, transaction: &Transaction, sender: Address) {
         fill_tx_env(tx_env, transaction, sender)
     }
 
+    /// Returns the chain specific accounts that should be part of the genesis state, in addition
+    /// to the allocations of the genesis file.
+    ///
+    /// Since these accounts are part of the genesis state root, they must be merged into the
+    /// [`Genesis`](reth_primitives::Genesis) of the chain spec, e.g. with `extend_accounts`,
+    /// before the genesis header is computed.
+    ///
+    /// This is useful for embedded test chains that need a deterministic genesis without a genesis
+    /// file. Empty by default.
+    fn genesis_alloc(&self) -> HashMap<Address, GenesisAccount> {
+        HashMap::new()
+    }
+
+    /// Returns the maximum number of transactions allowed per block by the chain configuration.
+    ///
+    /// Block executors reject blocks that exceed this limit before executing them. Standard
+    /// Ethereum has no such limit, so this returns `None` by default.
+    fn max_transactions_per_block(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the chain id of the chain the EVM is configured for.
+    ///
+    /// This lets consumers sign or validate transactions with the EVM configuration alone.
+    fn chain_id(&self) -> u64;
+
+    /// Returns the addresses of the chain specific precompiles activated at the given spec id, in
+    /// addition to the standard Ethereum precompiles.
+    ///
+    /// This lets validation layers recognize calls to added precompiles, e.g. on L2 chains. Empty
+    /// by default.
+    fn precompiles(&self, _spec_id: SpecId) -> &[Address] {
+        &[]
+    }
+
     /// Fill [`CfgEnvWithHandlerCfg`] fields according to the chain spec and given header
+    ///
+    /// The total difficulty is only needed for pre-merge blocks, `None` means that the block is
+    /// post-merge.
     fn fill_cfg_env(
         cfg_env: &mut CfgEnvWithHandlerCfg,
-        chain_spec: &ChainSpec,
+        chain_spec: &dyn ChainSpecLike,
         header: &Header,
-        total_difficulty: U256,
+        total_difficulty: Option<U256>,
     );
 
+    /// Returns the [`BlockEnv`] of the block following the given parent header, at the given
+    /// timestamp.
+    ///
+    /// The basefee is derived from the parent according to EIP-1559 and the excess blob gas
+    /// according to EIP-4844. Post-merge, the parent's mix hash is used as the prevrandao value
+    /// since the randao of the next block is not known yet, and the coinbase is set to the
+    /// parent's beneficiary. Both can be overwritten by the caller.
+    fn next_block_env(
+        &self,
+        parent: &Header,
+        timestamp: u64,
+        chain_spec: &dyn ChainSpecLike,
+    ) -> BlockEnv {
+        let hardforks = chain_spec.hardforks();
+        let number = parent.number + 1;
+        let after_merge = hardforks
+            .is_paris_active_at_block(number)
+            .unwrap_or_else(|| parent.difficulty.is_zero());
+
+        let base_fee_per_gas =
+            hardforks.fork(EthereumHardfork::London).active_at_block(number).then(|| {
+                parent
+                    .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp))
+                    .unwrap_or(EIP1559_INITIAL_BASE_FEE)
+            });
+        let excess_blob_gas = hardforks
+            .is_cancun_active_at_timestamp(timestamp)
+            .then(|| parent.next_block_excess_blob_gas().unwrap_or_default());
+
+        let header =
+            Header { number, timestamp, base_fee_per_gas, excess_blob_gas, ..parent.clone() };
+
+        let mut block_env = BlockEnv::default();
+        fill_block_env_with_coinbase(&mut block_env, &header, after_merge, parent.beneficiary);
+        block_env
+    }
+
+    /// Returns the highest gas limit that the block following the given parent header can have.
+    ///
+    /// The gas limit can increase by less than `1/1024` of the parent gas limit per block. At the
+    /// London transition, the parent gas limit is scaled by the elasticity multiplier first. The
+    /// base fee params are taken at the parent's timestamp.
+    fn estimate_gas_ceiling(&self, parent: &Header, chain_spec: &dyn ChainSpecLike) -> u64 {
+        let mut parent_gas_limit = parent.gas_limit;
+        if chain_spec
+            .hardforks()
+            .fork(EthereumHardfork::London)
+            .transitions_at_block(parent.number + 1)
+        {
+            let params = chain_spec.base_fee_params_at_timestamp(parent.timestamp);
+            parent_gas_limit = parent_gas_limit.saturating_mul(params.elasticity_multiplier as u64);
+        }
+
+        parent_gas_limit.saturating_add((parent_gas_limit / 1024).saturating_sub(1))
+    }
+
+    /// Fill [`BlockEnv`] fields according to the chain spec and given header, computing the
+    /// basefee from the parent header according to EIP-1559 instead of using the header's
+    /// `base_fee_per_gas`.
+    ///
+    /// This is useful for simulations where the header is built without a basefee. Before London
+    /// the basefee is left untouched.
+    fn fill_block_env_with_parent(
+        &self,
+        block_env: &mut BlockEnv,
+        chain_spec: &dyn ChainSpecLike,
+        header: &Header,
+        parent: &Header,
+        after_merge: bool,
+    ) {
+        let hardforks = chain_spec.hardforks();
+        fill_block_env_with_hardforks(
+            block_env,
+            chain_spec.chain(),
+            hardforks,
+            header,
+            after_merge,
+        );
+
+        if hardforks.fork(EthereumHardfork::London).active_at_block(header.number) {
+            let base_fee = parent
+                .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(header.timestamp))
+                .unwrap_or(EIP1559_INITIAL_BASE_FEE);
+            block_env.basefee = U256::from(base_fee);
+        }
+    }
+
+    /// Fill [`BlockEnv`] fields from the given block according to the chain spec.
+    ///
+    /// Whether the block is post-merge is derived from the chain spec, falling back to the
+    /// block's difficulty if the merge block of the chain is not known.
+    fn fill_block_env_from_block(
+        &self,
+        block_env: &mut BlockEnv,
+        block: &SealedBlock,
+        chain_spec: &dyn ChainSpecLike,
+    ) {
+        let hardforks = chain_spec.hardforks();
+        let after_merge = hardforks
+            .is_paris_active_at_block(block.number)
+            .unwrap_or_else(|| block.difficulty.is_zero());
+        fill_block_env_with_hardforks(
+            block_env,
+            chain_spec.chain(),
+            hardforks,
+            &block.header,
+            after_merge,
+        );
+    }
+
     /// Convenience function to call both [`fill_cfg_env`](ConfigureEvmEnv::fill_cfg_env) and
-    /// [`fill_block_env`].
+    /// [`fill_block_env`](reth_primitives::revm::env::fill_block_env).
+    ///
+    /// See [`fill_cfg_env`](ConfigureEvmEnv::fill_cfg_env) for the meaning of the total
+    /// difficulty.
     fn fill_cfg_and_block_env(
         cfg: &mut CfgEnvWithHandlerCfg,
         block_env: &mut BlockEnv,
-        chain_spec: &ChainSpec,
+        chain_spec: &dyn ChainSpecLike,
         header: &Header,
-        total_difficulty: U256,
+        total_difficulty: Option<U256>,
     ) {
         Self::fill_cfg_env(cfg, chain_spec, header, total_difficulty);
         let after_merge = cfg.handler_cfg.spec_id >= SpecId::MERGE;
-        fill_block_env(block_env, chain_spec, header, after_merge);
+        fill_block_env_with_hardforks(
+            block_env,
+            chain_spec.chain(),
+            chain_spec.hardforks(),
+            header,
+            after_merge,
+        );
+    }
+}
+
+/// Returns the [`Head`] that is used to determine the active hardforks of the given header.
+///
+/// The total difficulty is only needed for pre-merge blocks. `None` means that the block is
+/// post-merge, which is represented by a total difficulty of [`U256::MAX`].
+pub fn head_for_header(header: &Header, total_difficulty: Option<U256>) -> Head {
+    Head {
+        number: header.number,
+        timestamp: header.timestamp,
+        difficulty: header.difficulty,
+        total_difficulty: total_difficulty.unwrap_or(U256::MAX),
+        hash: Default::default(),
     }
 }
@@ -1,16 +1,26 @@
 //! A no operation block executor implementation.
 
-use std::fmt::Display;
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
+use reth_primitives::{Address, BlockNumber, BlockWithSenders, Header, Receipt, Transaction, U256};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
-use revm_primitives::db::Database;
+use revm_primitives::{db::Database, CfgEnv, CfgEnvWithHandlerCfg, TxEnv};
 
-use crate::execute::{
-    BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
+use crate::{
+    execute::{
+        BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
+    },
+    ChainSpecLike, ConfigureEvmEnv,
 };
 
 const UNAVAILABLE_FOR_NOOP: &str = "execution unavailable for noop";
@@ -69,3 +79,153 @@ impl<DB> BatchExecutor<DB> for NoopBlockExecutorProvider {
         None
     }
 }
+
+/// A [`BlockExecutorProvider`] implementation that successfully executes every block without any
+/// state changes and counts the number of executed blocks.
+///
+/// The provider is also its own [`Executor`] and [`BatchExecutor`]: all executors created by it are
+/// clones sharing the same counter, which makes it possible to verify how many blocks were
+/// executed, e.g. by a pipeline stage in tests. The `MockExecutorFactory` of the `test-utils`
+/// feature builds on it to also record which blocks were executed.
+#[derive(Debug, Default, Clone)]
+pub struct NoopBlockExecutor {
+    /// The number of executed blocks, shared by all clones.
+    call_count: Arc<AtomicUsize>,
+}
+
+impl NoopBlockExecutor {
+    /// Returns the number of blocks executed by this executor and all of its clones.
+    pub fn call_count(&self) -> usize {
+        self.call_count.load(Ordering::Relaxed)
+    }
+
+    /// Increments the call counter.
+    fn on_execute(&self) {
+        self.call_count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl BlockExecutorProvider for NoopBlockExecutor {
+    type Executor<DB: Database<Error: Into<ProviderError> + Display>> = Self;
+
+    type BatchExecutor<DB: Database<Error: Into<ProviderError> + Display>> = Self;
+
+    fn executor<DB>(&self, _: DB) -> Self::Executor<DB>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        self.clone()
+    }
+
+    fn batch_executor<DB>(&self, _: DB, _: PruneModes) -> Self::BatchExecutor<DB>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        self.clone()
+    }
+}
+
+impl<DB> Executor<DB> for NoopBlockExecutor {
+    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
+    type Output = BlockExecutionOutput<Receipt>;
+    type Error = BlockExecutionError;
+
+    fn execute(self, _: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
+        self.on_execute();
+        Ok(BlockExecutionOutput {
+            state: Default::default(),
+            receipts: Vec::new(),
+            requests: Vec::new(),
+            gas_used: 0,
+            blob_gas_used: None,
+        })
+    }
+}
+
+impl<DB> BatchExecutor<DB> for NoopBlockExecutor {
+    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
+    type Output = ExecutionOutcome;
+    type Error = BlockExecutionError;
+
+    fn execute_and_verify_one(&mut self, _: Self::Input<'_>) -> Result<(), Self::Error> {
+        self.on_execute();
+        Ok(())
+    }
+
+    fn finalize(self) -> Self::Output {
+        ExecutionOutcome::default()
+    }
+
+    fn set_tip(&mut self, _: BlockNumber) {}
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A [`ConfigureEvmEnv`] implementation that ignores all inputs.
+///
+/// Transaction environments are left at their defaults and the configuration environment is not
+/// modified, so tests can set up the environment themselves. The chain id is the one of a default
+/// configuration environment.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct NoopConfigureEvmEnv;
+
+impl ConfigureEvmEnv for NoopConfigureEvmEnv {
+    type Error = Infallible;
+
+    fn fill_tx_env_unsigned(&self, _: &mut TxEnv, _: &Transaction, _: Address) {}
+
+    fn fill_cfg_env(
+        _: &mut CfgEnvWithHandlerCfg,
+        _: &dyn ChainSpecLike,
+        _: &Header,
+        _: Option<U256>,
+    ) {
+    }
+
+    fn chain_id(&self) -> u64 {
+        CfgEnv::default().chain_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use revm::db::{CacheDB, EmptyDBTyped};
+
+    #[test]
+    fn counts_executed_blocks() {
+        let provider = NoopBlockExecutor::default();
+        let block = BlockWithSenders::default();
+
+        let db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+        provider.executor(db.clone()).execute((&block, U256::ZERO).into()).unwrap();
+
+        let mut batch = provider.batch_executor(db, PruneModes::none());
+        batch.execute_and_verify_one((&block, U256::ZERO).into()).unwrap();
+        batch.execute_and_verify_one((&block, U256::ZERO).into()).unwrap();
+
+        assert_eq!(provider.call_count(), 3);
+    }
+
+    #[test]
+    fn noop_env_ignores_inputs() {
+        let evm_config = NoopConfigureEvmEnv::default();
+
+        let transaction = Transaction::Legacy(Default::default());
+        let tx_env = evm_config.tx_env_unsigned(&transaction, Address::with_last_byte(1));
+        assert_eq!(tx_env, TxEnv::default());
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        NoopConfigureEvmEnv::fill_cfg_env(
+            &mut cfg,
+            &*reth_chainspec::MAINNET,
+            &Header::default(),
+            None,
+        );
+        assert_eq!(cfg, CfgEnvWithHandlerCfg::new(Default::default(), Default::default()));
+    }
+}
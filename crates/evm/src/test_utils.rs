@@ -1,12 +1,15 @@
 //! Helpers for testing.
 
-use crate::execute::{
-    BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
+use crate::{
+    execute::{
+        BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
+    },
+    noop::NoopBlockExecutor,
 };
 use parking_lot::Mutex;
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
+use reth_primitives::{BlockNumber, BlockWithSenders, Receipt, SealedBlock};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
 use revm_primitives::db::Database;
@@ -58,6 +61,7 @@ impl<DB> Executor<DB> for MockExecutorProvider {
             receipts: receipts.into_iter().flatten().flatten().collect(),
             requests: requests.into_iter().flatten().collect(),
             gas_used: 0,
+            blob_gas_used: None,
         })
     }
 }
@@ -81,3 +85,109 @@ impl<DB> BatchExecutor<DB> for MockExecutorProvider {
         None
     }
 }
+
+/// A [`BlockExecutorProvider`] that records all blocks it executes, in order, across all of its
+/// executors.
+///
+/// Every execution succeeds without any state changes, as with [`NoopBlockExecutor`].
+#[derive(Clone, Debug, Default)]
+pub struct MockExecutorFactory {
+    inner: NoopBlockExecutor,
+    executed_blocks: Arc<Mutex<Vec<SealedBlock>>>,
+}
+
+impl MockExecutorFactory {
+    /// Returns all blocks executed so far, in execution order.
+    pub fn executed_blocks(&self) -> Vec<SealedBlock> {
+        self.executed_blocks.lock().clone()
+    }
+
+    /// Records the given block as executed.
+    fn record(&self, block: &BlockWithSenders) {
+        self.executed_blocks.lock().push(block.block.clone().seal_slow());
+    }
+}
+
+impl BlockExecutorProvider for MockExecutorFactory {
+    type Executor<DB: Database<Error: Into<ProviderError> + Display>> = Self;
+
+    type BatchExecutor<DB: Database<Error: Into<ProviderError> + Display>> = Self;
+
+    fn executor<DB>(&self, _: DB) -> Self::Executor<DB>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        self.clone()
+    }
+
+    fn batch_executor<DB>(&self, _: DB, _: PruneModes) -> Self::BatchExecutor<DB>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        self.clone()
+    }
+}
+
+impl<DB> Executor<DB> for MockExecutorFactory {
+    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
+    type Output = BlockExecutionOutput<Receipt>;
+    type Error = BlockExecutionError;
+
+    fn execute(self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
+        self.record(input.block);
+        Executor::<DB>::execute(self.inner, input)
+    }
+}
+
+impl<DB> BatchExecutor<DB> for MockExecutorFactory {
+    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
+    type Output = ExecutionOutcome;
+    type Error = BlockExecutionError;
+
+    fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
+        self.record(input.block);
+        BatchExecutor::<DB>::execute_and_verify_one(&mut self.inner, input)
+    }
+
+    fn finalize(self) -> Self::Output {
+        BatchExecutor::<DB>::finalize(self.inner)
+    }
+
+    fn set_tip(&mut self, _: BlockNumber) {}
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block, Header, U256};
+    use revm::db::{CacheDB, EmptyDBTyped};
+
+    #[test]
+    fn records_executed_blocks_in_order() {
+        let factory = MockExecutorFactory::default();
+        let blocks = (1..=3)
+            .map(|number| {
+                let header = Header { number, ..Default::default() };
+                BlockWithSenders { block: Block { header, ..Default::default() }, senders: vec![] }
+            })
+            .collect::<Vec<_>>();
+
+        let db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+        factory.executor(db.clone()).execute((&blocks[0], U256::ZERO).into()).unwrap();
+
+        let mut batch = factory.batch_executor(db, PruneModes::none());
+        batch.execute_and_verify_one((&blocks[1], U256::ZERO).into()).unwrap();
+        batch.execute_and_verify_one((&blocks[2], U256::ZERO).into()).unwrap();
+
+        let executed = factory.executed_blocks();
+        assert_eq!(
+            executed.iter().map(|block| block.number).collect::<Vec<_>>(),
+            blocks.iter().map(|block| block.header.number).collect::<Vec<_>>()
+        );
+        assert_eq!(factory.inner.call_count(), 3);
+    }
+}
@@ -8,7 +8,8 @@ use futures::Stream;
 use reth_consensus::{Consensus, ConsensusError};
 use reth_network_peers::WithPeerId;
 use reth_primitives::{
-    BlockBody, GotExpected, Header, HeadersDirection, SealedBlock, SealedHeader, B256,
+    BlockBody, BlockBodyError, GotExpected, Header, HeadersDirection, SealedBlock, SealedHeader,
+    B256,
 };
 use std::{
     cmp::Reverse,
@@ -295,6 +296,7 @@ enum BodyResponse {
 ///   - ommer hash
 ///   - transaction root
 ///   - withdrawals root
+///   - requests root
 fn ensure_valid_body_response(
     header: &SealedHeader,
     block: &BlockBody,
@@ -306,44 +308,19 @@ fn ensure_valid_body_response(
         ))
     }
 
-    let tx_root = block.calculate_tx_root();
-    if header.transactions_root != tx_root {
-        return Err(ConsensusError::BodyTransactionRootDiff(
-            GotExpected { got: tx_root, expected: header.transactions_root }.into(),
-        ))
-    }
-
-    match (header.withdrawals_root, &block.withdrawals) {
-        (Some(header_withdrawals_root), Some(withdrawals)) => {
-            let withdrawals = withdrawals.as_slice();
-            let withdrawals_root = reth_primitives::proofs::calculate_withdrawals_root(withdrawals);
-            if withdrawals_root != header_withdrawals_root {
-                return Err(ConsensusError::BodyWithdrawalsRootDiff(
-                    GotExpected { got: withdrawals_root, expected: header_withdrawals_root }.into(),
-                ))
-            }
+    block.validate(header).map_err(|err| match err {
+        BlockBodyError::TransactionsRootMismatch { expected, got } => {
+            ConsensusError::BodyTransactionRootDiff(GotExpected { got, expected }.into())
         }
-        (None, None) => {
-            // this is ok because we assume the fork is not active in this case
+        BlockBodyError::WithdrawalsRootMismatch { expected: Some(expected), got: Some(got) } => {
+            ConsensusError::BodyWithdrawalsRootDiff(GotExpected { got, expected }.into())
         }
-        _ => return Err(ConsensusError::WithdrawalsRootUnexpected),
-    }
-
-    match (header.requests_root, &block.requests) {
-        (Some(header_requests_root), Some(requests)) => {
-            let requests = requests.0.as_slice();
-            let requests_root = reth_primitives::proofs::calculate_requests_root(requests);
-            if requests_root != header_requests_root {
-                return Err(ConsensusError::BodyRequestsRootDiff(
-                    GotExpected { got: requests_root, expected: header_requests_root }.into(),
-                ))
-            }
+        BlockBodyError::WithdrawalsRootMismatch { .. } => ConsensusError::WithdrawalsRootUnexpected,
+        BlockBodyError::RequestsRootMismatch { expected: Some(expected), got: Some(got) } => {
+            ConsensusError::BodyRequestsRootDiff(GotExpected { got, expected }.into())
         }
-        (None, None) => {
-            // this is ok because we assume the fork is not active in this case
-        }
-        _ => return Err(ConsensusError::RequestsRootUnexpected),
-    }
+        BlockBodyError::RequestsRootMismatch { .. } => ConsensusError::RequestsRootUnexpected,
+    })?;
 
     Ok(())
 }
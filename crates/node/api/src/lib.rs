@@ -17,7 +17,7 @@ pub use reth_payload_primitives as payload;
 pub use reth_payload_primitives::*;
 
 /// Traits and helper types used to abstract over EVM methods and types.
-pub use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+pub use reth_evm::{ChainSpecLike, ConfigureEvm, ConfigureEvmEnv};
 
 pub mod primitives;
 
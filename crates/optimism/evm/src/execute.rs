@@ -2,21 +2,25 @@
 
 use crate::{l1::ensure_create2_deployer, OptimismBlockExecutionError, OptimismEvmConfig};
 use reth_chainspec::{ChainSpec, EthereumHardforks, OptimismHardfork};
+use reth_consensus_common::validation::validate_block_transaction_count;
 use reth_evm::{
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
-        BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
+        BlockExecutor, BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
+        TransactionSnapshots,
     },
     ConfigureEvm,
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_optimism_consensus::validate_block_post_execution;
-use reth_primitives::{BlockNumber, BlockWithSenders, Header, Receipt, Receipts, TxType, U256};
+use reth_primitives::{
+    BlockNumber, BlockWithSenders, Header, Receipt, Receipts, TxType, B256, U256,
+};
 use reth_prune_types::PruneModes;
 use reth_revm::{
     batch::{BlockBatchRecord, BlockExecutorStats},
     db::states::bundle_state::BundleRetention,
-    state_change::{apply_beacon_root_contract_call, post_block_balance_increments},
+    state_change::{apply_pre_block_calls, post_block_balance_increments},
     Evm, State,
 };
 use revm_primitives::{
@@ -36,7 +40,8 @@ pub struct OpExecutorProvider<EvmConfig = OptimismEvmConfig> {
 impl OpExecutorProvider {
     /// Creates a new default optimism executor provider.
     pub fn optimism(chain_spec: Arc<ChainSpec>) -> Self {
-        Self::new(chain_spec, Default::default())
+        let evm_config = OptimismEvmConfig::new(&chain_spec);
+        Self::new(chain_spec, evm_config)
     }
 }
 
@@ -112,23 +117,28 @@ where
     /// # Note
     ///
     /// It does __not__ apply post-execution changes.
+    ///
+    /// The gas used by the executed transactions is accumulated in `cumulative_gas_used`.
     fn execute_pre_and_transactions<Ext, DB>(
         &self,
         block: &BlockWithSenders,
+        parent_beacon_block_root: Option<B256>,
         mut evm: Evm<'_, Ext, &mut State<DB>>,
+        cumulative_gas_used: &mut u64,
+        snapshots: &mut TransactionSnapshots,
     ) -> Result<(Vec<Receipt>, u64), BlockExecutionError>
     where
         DB: Database<Error: Into<ProviderError> + std::fmt::Display>,
     {
-        // apply pre execution changes
-        apply_beacon_root_contract_call(
-            &self.chain_spec,
-            block.timestamp,
-            block.number,
-            block.parent_beacon_block_root,
-            &mut evm,
+        // validate the transaction count against the limit of the evm configuration
+        validate_block_transaction_count(
+            block.body.len(),
+            self.evm_config.max_transactions_per_block(),
         )?;
 
+        // apply pre execution changes, the EIP-2935 update is only applied once Prague is active
+        apply_pre_block_calls(&self.chain_spec, &block.header, parent_beacon_block_root, &mut evm)?;
+
         // execute transactions
         let is_regolith =
             self.chain_spec.fork(OptimismHardfork::Regolith).active_at_timestamp(block.timestamp);
@@ -140,12 +150,13 @@ where
         ensure_create2_deployer(self.chain_spec.clone(), block.timestamp, evm.db_mut())
             .map_err(|_| OptimismBlockExecutionError::ForceCreate2DeployerFail)?;
 
-        let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
         for (sender, transaction) in block.transactions_with_sender() {
+            snapshots.take(evm.db_mut(), *cumulative_gas_used);
+
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
-            let block_available_gas = block.header.gas_limit - cumulative_gas_used;
+            let block_available_gas = block.header.gas_limit - *cumulative_gas_used;
             if transaction.gas_limit() > block_available_gas &&
                 (is_regolith || !transaction.is_system_transaction())
             {
@@ -202,7 +213,7 @@ where
             evm.db_mut().commit(state);
 
             // append gas used
-            cumulative_gas_used += result.gas_used();
+            *cumulative_gas_used += result.gas_used();
 
             // Push transaction changeset and calculate header bloom filter for receipt.
             receipts.push(Receipt {
@@ -210,7 +221,7 @@ where
                 // Success flag was added in `EIP-658: Embedding transaction status code in
                 // receipts`.
                 success: result.is_success(),
-                cumulative_gas_used,
+                cumulative_gas_used: *cumulative_gas_used,
                 logs: result.into_logs(),
                 deposit_nonce: depositor.map(|account| account.nonce),
                 // The deposit receipt version was introduced in Canyon to indicate an update to how
@@ -224,7 +235,7 @@ where
         }
         drop(evm);
 
-        Ok((receipts, cumulative_gas_used))
+        Ok((receipts, *cumulative_gas_used))
     }
 }
 
@@ -239,12 +250,21 @@ pub struct OpBlockExecutor<EvmConfig, DB> {
     executor: OpEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// The gas used by the transactions of the current block that were executed so far.
+    gas_used: u64,
+    /// The state snapshots taken before the transactions of the current block.
+    snapshots: TransactionSnapshots,
 }
 
 impl<EvmConfig, DB> OpBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: OpEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: OpEvmExecutor { chain_spec, evm_config },
+            state,
+            gas_used: 0,
+            snapshots: TransactionSnapshots::new(),
+        }
     }
 
     #[inline]
@@ -275,7 +295,7 @@ where
             &mut block_env,
             self.chain_spec(),
             header,
-            total_difficulty,
+            Some(total_difficulty),
         );
 
         EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default())
@@ -290,6 +310,7 @@ where
         &mut self,
         block: &BlockWithSenders,
         total_difficulty: U256,
+        parent_beacon_block_root: Option<B256>,
     ) -> Result<(Vec<Receipt>, u64), BlockExecutionError> {
         // 1. prepare state on new block
         self.on_new_block(&block.header);
@@ -299,7 +320,13 @@ where
 
         let (receipts, gas_used) = {
             let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_pre_and_transactions(block, evm)
+            self.executor.execute_pre_and_transactions(
+                block,
+                parent_beacon_block_root,
+                evm,
+                &mut self.gas_used,
+                &mut self.snapshots,
+            )
         }?;
 
         // 3. apply post execution changes
@@ -310,6 +337,9 @@ where
 
     /// Apply settings before a new block is executed.
     pub(crate) fn on_new_block(&mut self, header: &Header) {
+        self.gas_used = 0;
+        self.snapshots.clear();
+
         // Set state clear flag if the block is after the Spurious Dragon hardfork.
         let state_clear_flag = self.chain_spec().is_spurious_dragon_active_at_block(header.number);
         self.state.set_state_clear_flag(state_clear_flag);
@@ -350,8 +380,29 @@ where
     ///
     /// State changes are committed to the database.
     fn execute(mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
-        let BlockExecutionInput { block, total_difficulty } = input;
-        let (receipts, gas_used) = self.execute_without_verification(block, total_difficulty)?;
+        self.execute_block(input)
+    }
+}
+
+impl<EvmConfig, DB> BlockExecutor<DB> for OpBlockExecutor<EvmConfig, DB>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error: Into<ProviderError> + std::fmt::Display>,
+{
+    fn execute_block(&mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
+        let BlockExecutionInput { block, total_difficulty, parent_beacon_block_root } = input;
+        let (receipts, gas_used) = self.execute_without_verification(
+            block,
+            total_difficulty,
+            parent_beacon_block_root.or(block.parent_beacon_block_root),
+        )?;
+
+        // blob transactions are not supported on optimism
+        let blob_gas_used =
+            self.chain_spec().is_cancun_active_at_timestamp(block.timestamp).then_some(0);
+
+        // the snapshots do not track merged transitions
+        self.snapshots.clear();
 
         // NOTE: we need to merge keep the reverts for the bundle retention
         self.state.merge_transitions(BundleRetention::Reverts);
@@ -361,8 +412,26 @@ where
             receipts,
             requests: vec![],
             gas_used,
+            blob_gas_used,
         })
     }
+
+    fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    fn enable_transaction_snapshots(&mut self) {
+        self.snapshots.enable();
+    }
+
+    fn revert_to(&mut self, tx_index: usize) -> Result<(), BlockExecutionError> {
+        self.gas_used = self.snapshots.revert_to(&mut self.state, tx_index)?;
+        Ok(())
+    }
+
+    fn into_state(self) -> State<DB> {
+        self.state
+    }
 }
 
 /// An executor for a batch of blocks.
@@ -400,9 +469,12 @@ where
     type Error = BlockExecutionError;
 
     fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
-        let BlockExecutionInput { block, total_difficulty } = input;
-        let (receipts, _gas_used) =
-            self.executor.execute_without_verification(block, total_difficulty)?;
+        let BlockExecutionInput { block, total_difficulty, parent_beacon_block_root } = input;
+        let (receipts, _gas_used) = self.executor.execute_without_verification(
+            block,
+            total_difficulty,
+            parent_beacon_block_root.or(block.parent_beacon_block_root),
+        )?;
 
         validate_block_post_execution(block, self.executor.chain_spec(), &receipts)?;
 
@@ -9,17 +9,18 @@
 // The `optimism` feature must be enabled to use this crate.
 #![cfg(feature = "optimism")]
 
-use reth_chainspec::ChainSpec;
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use core::convert::Infallible;
+use reth_chainspec::{Chain, ChainSpec};
+use reth_evm::{head_for_header, ChainSpecLike, ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
     revm::env::fill_op_tx_env,
     revm_primitives::{AnalysisKind, CfgEnvWithHandlerCfg, TxEnv},
-    Address, Head, Header, TransactionSigned, U256,
+    Address, Bytes, Header, Transaction, TransactionSigned, U256,
 };
 use reth_revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 
 mod config;
-pub use config::{revm_spec, revm_spec_by_timestamp_after_bedrock};
+pub use config::{revm_spec, revm_spec_by_hardforks, revm_spec_by_timestamp_after_bedrock};
 mod execute;
 pub use execute::*;
 pub mod l1;
@@ -29,32 +30,52 @@ mod error;
 pub use error::OptimismBlockExecutionError;
 
 /// Optimism-related EVM configuration.
-#[derive(Debug, Default, Clone, Copy)]
-#[non_exhaustive]
-pub struct OptimismEvmConfig;
+///
+/// The default configuration is bound to OP mainnet, use [`OptimismEvmConfig::new`] for other
+/// chains.
+#[derive(Debug, Clone, Copy)]
+pub struct OptimismEvmConfig {
+    /// The chain id of the chain spec the configuration was created for.
+    chain_id: u64,
+}
+
+impl OptimismEvmConfig {
+    /// Creates a new configuration for the given chain spec.
+    pub const fn new(chain_spec: &ChainSpec) -> Self {
+        Self { chain_id: chain_spec.chain().id() }
+    }
+}
+
+impl Default for OptimismEvmConfig {
+    fn default() -> Self {
+        Self { chain_id: Chain::optimism_mainnet().id() }
+    }
+}
 
 impl ConfigureEvmEnv for OptimismEvmConfig {
+    type Error = Infallible;
+
     fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
         let mut buf = Vec::with_capacity(transaction.length_without_header());
         transaction.encode_enveloped(&mut buf);
         fill_op_tx_env(tx_env, transaction, sender, buf.into());
     }
 
+    fn fill_tx_env_unsigned(&self, tx_env: &mut TxEnv, transaction: &Transaction, sender: Address) {
+        // The envelope of an unsigned transaction is unknown, submit zero bytes for the enveloped
+        // tx size so no L1 fee is charged.
+        fill_op_tx_env(tx_env, transaction, sender, Bytes::default());
+    }
+
     fn fill_cfg_env(
         cfg_env: &mut CfgEnvWithHandlerCfg,
-        chain_spec: &ChainSpec,
+        chain_spec: &dyn ChainSpecLike,
         header: &Header,
-        total_difficulty: U256,
+        total_difficulty: Option<U256>,
     ) {
-        let spec_id = revm_spec(
-            chain_spec,
-            &Head {
-                number: header.number,
-                timestamp: header.timestamp,
-                difficulty: header.difficulty,
-                total_difficulty,
-                hash: Default::default(),
-            },
+        let spec_id = revm_spec_by_hardforks(
+            chain_spec.hardforks(),
+            &head_for_header(header, total_difficulty),
         );
 
         cfg_env.chain_id = chain_spec.chain().id();
@@ -63,6 +84,10 @@ impl ConfigureEvmEnv for OptimismEvmConfig {
         cfg_env.handler_cfg.spec_id = spec_id;
         cfg_env.handler_cfg.is_optimism = chain_spec.is_optimism();
     }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
 }
 
 impl ConfigureEvm for OptimismEvmConfig {
@@ -89,6 +114,7 @@ impl ConfigureEvm for OptimismEvmConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_chainspec::ChainSpec;
     use reth_primitives::revm_primitives::{BlockEnv, CfgEnv};
     use revm_primitives::SpecId;
 
@@ -106,7 +132,7 @@ mod tests {
             &mut block_env,
             &chain_spec,
             &header,
-            total_difficulty,
+            Some(total_difficulty),
         );
 
         assert_eq!(cfg_env.chain_id, chain_spec.chain().id());
@@ -110,7 +110,7 @@ where
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
-        let evm_config = OptimismEvmConfig::default();
+        let evm_config = OptimismEvmConfig::new(&chain_spec);
         let executor = OpExecutorProvider::new(chain_spec, evm_config);
 
         Ok((evm_config, executor))
@@ -963,7 +963,9 @@ where
         attributes.parent_beacon_block_root(),
         &mut evm_pre_block,
     )
-    .map_err(|err| PayloadBuilderError::Internal(err.into()))
+    .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+
+    Ok(())
 }
 
 /// Apply the [EIP-7002](https://eips.ethereum.org/EIPS/eip-7002) post block contract call.
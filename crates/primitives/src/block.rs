@@ -590,6 +590,39 @@ impl BlockBody {
         self.requests.as_ref().map(|r| crate::proofs::calculate_requests_root(&r.0))
     }
 
+    /// Ensures that the transactions, withdrawals and requests of the body are consistent with
+    /// the roots of the given header.
+    ///
+    /// A withdrawals or requests root mismatch is also returned if the root is set in the header
+    /// but the body has no withdrawals or requests, and vice versa.
+    pub fn validate(&self, header: &Header) -> Result<(), BlockBodyError> {
+        let transactions_root = self.calculate_tx_root();
+        if header.transactions_root != transactions_root {
+            return Err(BlockBodyError::TransactionsRootMismatch {
+                expected: header.transactions_root,
+                got: transactions_root,
+            })
+        }
+
+        let withdrawals_root = self.calculate_withdrawals_root();
+        if header.withdrawals_root != withdrawals_root {
+            return Err(BlockBodyError::WithdrawalsRootMismatch {
+                expected: header.withdrawals_root,
+                got: withdrawals_root,
+            })
+        }
+
+        let requests_root = self.calculate_requests_root();
+        if header.requests_root != requests_root {
+            return Err(BlockBodyError::RequestsRootMismatch {
+                expected: header.requests_root,
+                got: requests_root,
+            })
+        }
+
+        Ok(())
+    }
+
     /// Calculates a heuristic for the in-memory size of the [`BlockBody`].
     #[inline]
     pub fn size(&self) -> usize {
@@ -603,6 +636,35 @@ impl BlockBody {
     }
 }
 
+/// Error returned when a [`BlockBody`] is inconsistent with its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror_no_std::Error)]
+pub enum BlockBodyError {
+    /// The transactions root of the body does not match the header.
+    #[error("mismatched transactions root: got {got}, expected {expected}")]
+    TransactionsRootMismatch {
+        /// The transactions root of the header.
+        expected: B256,
+        /// The transactions root calculated from the body.
+        got: B256,
+    },
+    /// The withdrawals root of the body does not match the header.
+    #[error("mismatched withdrawals root: got {got:?}, expected {expected:?}")]
+    WithdrawalsRootMismatch {
+        /// The withdrawals root of the header.
+        expected: Option<B256>,
+        /// The withdrawals root calculated from the body.
+        got: Option<B256>,
+    },
+    /// The requests root of the body does not match the header.
+    #[error("mismatched requests root: got {got:?}, expected {expected:?}")]
+    RequestsRootMismatch {
+        /// The requests root of the header.
+        expected: Option<B256>,
+        /// The requests root calculated from the body.
+        got: Option<B256>,
+    },
+}
+
 impl From<Block> for BlockBody {
     fn from(block: Block) -> Self {
         Self {
@@ -807,4 +869,46 @@ mod tests {
             Some(SealedBlockWithSenders { block: sealed, senders: vec![sender] })
         );
     }
+
+    #[test]
+    fn block_body_validate() {
+        let body = BlockBody {
+            transactions: vec![TransactionSigned::default()],
+            withdrawals: Some(Withdrawals::default()),
+            ..Default::default()
+        };
+        let header = Header {
+            transactions_root: body.calculate_tx_root(),
+            withdrawals_root: body.calculate_withdrawals_root(),
+            ..Default::default()
+        };
+        assert_eq!(body.validate(&header), Ok(()));
+
+        let mismatch = Header { transactions_root: B256::ZERO, ..header.clone() };
+        assert_eq!(
+            body.validate(&mismatch),
+            Err(BlockBodyError::TransactionsRootMismatch {
+                expected: B256::ZERO,
+                got: header.transactions_root
+            })
+        );
+
+        let missing_withdrawals = BlockBody { withdrawals: None, ..body.clone() };
+        assert_eq!(
+            missing_withdrawals.validate(&header),
+            Err(BlockBodyError::WithdrawalsRootMismatch {
+                expected: header.withdrawals_root,
+                got: None
+            })
+        );
+
+        let unexpected_requests = BlockBody { requests: Some(Requests::default()), ..body };
+        assert_eq!(
+            unexpected_requests.validate(&header),
+            Err(BlockBodyError::RequestsRootMismatch {
+                expected: None,
+                got: unexpected_requests.calculate_requests_root()
+            })
+        );
+    }
 }
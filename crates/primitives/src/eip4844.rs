@@ -1,5 +1,15 @@
 //! Helpers for working with EIP-4844 blob fee.
 
+use crate::{
+    constants::eip4844::{DATA_GAS_PER_BLOB, FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENT_BYTES},
+    B256,
+};
+#[cfg(feature = "c-kzg")]
+use crate::{
+    kzg::{self, KzgSettings},
+    BlobTransactionSidecar, BlobTransactionValidationError, Transaction, TransactionSigned,
+};
+
 // re-exports from revm for calculating blob fee
 pub use crate::revm_primitives::{
     calc_blob_gasprice, calc_excess_blob_gas as calculate_excess_blob_gas,
@@ -7,3 +17,233 @@ pub use crate::revm_primitives::{
 
 #[doc(inline)]
 pub use alloy_eips::eip4844::kzg_to_versioned_hash;
+
+/// The maximum number of blobs that can be included in a block, as of Cancun.
+#[doc(inline)]
+pub use crate::constants::eip4844::MAX_BLOBS_PER_BLOCK;
+
+/// Calculates the fee per byte of blob data, given the excess blob gas of the block.
+///
+/// This is the cost of a blob, `DATA_GAS_PER_BLOB` at the blob gas price, divided by the number of
+/// bytes in a blob. Since blob gas is currently charged one unit per byte, this is equal to the
+/// blob gas price.
+pub fn calc_blob_fee_per_byte(excess_blob_gas: u64) -> u128 {
+    let bytes_per_blob = (FIELD_ELEMENTS_PER_BLOB * FIELD_ELEMENT_BYTES) as u128;
+    calc_blob_gasprice(excess_blob_gas) * DATA_GAS_PER_BLOB as u128 / bytes_per_blob
+}
+
+/// Returns the blob gas used by the given number of blobs.
+///
+/// Each blob uses [`DATA_GAS_PER_BLOB`] (`2**17`) blob gas, regardless of its content.
+pub const fn blob_gas_used(blob_count: usize) -> u64 {
+    DATA_GAS_PER_BLOB * blob_count as u64
+}
+
+/// Calculates the excess blob gas of the block following a block with the given excess blob gas
+/// and number of blobs.
+///
+/// This is [`calculate_excess_blob_gas`] with the blob gas used derived from the blob count.
+pub fn calculate_excess_blob_gas_after_block(
+    parent_excess_blob_gas: u64,
+    blob_count: usize,
+) -> u64 {
+    calculate_excess_blob_gas(parent_excess_blob_gas, blob_gas_used(blob_count))
+}
+
+/// Validates the blob sidecar of the given EIP-4844 transaction.
+///
+/// This checks that the sidecar has as many blobs, commitments and proofs as the transaction has
+/// versioned hashes, that each blob verifies against its commitment and proof, and that the
+/// versioned hashes of the commitments match the versioned hashes of the transaction.
+///
+/// Returns [`BlobTransactionValidationError::NotBlobTransaction`] if the transaction is not an
+/// EIP-4844 transaction.
+#[cfg(feature = "c-kzg")]
+pub fn validate_blob_transaction_sidecar(
+    tx: &TransactionSigned,
+    sidecar: &BlobTransactionSidecar,
+    proof_settings: &KzgSettings,
+) -> Result<(), BlobTransactionValidationError> {
+    match &tx.transaction {
+        Transaction::Eip4844(tx) => tx.validate_blob(sidecar, proof_settings),
+        _ => Err(BlobTransactionValidationError::NotBlobTransaction(tx.tx_type().into())),
+    }
+}
+
+/// Errors that can occur when validating a blob sidecar against the versioned hashes of its
+/// transaction with `validate_blob_sidecar`.
+///
+/// Unlike `BlobTransactionValidationError`, every failure identifies the offending blob.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror_no_std::Error)]
+pub enum BlobSidecarValidationError {
+    /// The number of blobs, commitments or proofs of the sidecar does not match the number of
+    /// versioned hashes of the transaction.
+    #[error("expected {expected} blob commitments, got {got}")]
+    CommitmentCountMismatch {
+        /// The number of versioned hashes of the transaction.
+        expected: usize,
+        /// The number of blobs, commitments or proofs of the sidecar.
+        got: usize,
+    },
+    /// The KZG proof of the blob at the given index does not verify against its commitment.
+    #[error("invalid KZG proof for blob {index}")]
+    KzgProofInvalid {
+        /// The index of the blob in the sidecar.
+        index: usize,
+    },
+    /// The versioned hash of the commitment at the given index does not match the versioned hash
+    /// of the transaction.
+    #[error("versioned hash mismatch for blob {index}: expected {expected}, got {got}")]
+    VersionedHashMismatch {
+        /// The index of the blob in the sidecar.
+        index: usize,
+        /// The versioned hash of the transaction.
+        expected: B256,
+        /// The versioned hash of the sidecar commitment.
+        got: B256,
+    },
+}
+
+/// Validates the blob sidecar against the given versioned hashes, reporting which blob failed.
+///
+/// This performs the same checks as [`validate_blob_transaction_sidecar`], but verifies the KZG
+/// proofs one by one instead of in a batch, so it is slower and better suited for diagnostics.
+#[cfg(feature = "c-kzg")]
+pub fn validate_blob_sidecar(
+    versioned_hashes: &[B256],
+    sidecar: &BlobTransactionSidecar,
+    proof_settings: &KzgSettings,
+) -> Result<(), BlobSidecarValidationError> {
+    let expected = versioned_hashes.len();
+    for got in [sidecar.blobs.len(), sidecar.commitments.len(), sidecar.proofs.len()] {
+        if got != expected {
+            return Err(BlobSidecarValidationError::CommitmentCountMismatch { expected, got })
+        }
+    }
+
+    for (index, (expected, commitment)) in
+        versioned_hashes.iter().zip(&sidecar.commitments).enumerate()
+    {
+        let got = kzg_to_versioned_hash(commitment.as_slice());
+        if got != *expected {
+            return Err(BlobSidecarValidationError::VersionedHashMismatch {
+                index,
+                expected: *expected,
+                got,
+            })
+        }
+    }
+
+    for (index, ((blob, commitment), proof)) in
+        sidecar.blobs.iter().zip(&sidecar.commitments).zip(&sidecar.proofs).enumerate()
+    {
+        let verified = kzg::Blob::from_bytes(blob.as_slice()).and_then(|blob| {
+            kzg::KzgProof::verify_blob_kzg_proof(
+                &blob,
+                &kzg::Bytes48::from_bytes(commitment.as_slice())?,
+                &kzg::Bytes48::from_bytes(proof.as_slice())?,
+                proof_settings,
+            )
+        });
+        if !verified.unwrap_or(false) {
+            return Err(BlobSidecarValidationError::KzgProofInvalid { index })
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "c-kzg")]
+    use crate::{Signature, TxLegacy};
+
+    #[test]
+    fn blob_fee_per_byte() {
+        for excess_blob_gas in [0, 10_000_000, 100_000_000] {
+            assert_eq!(
+                calc_blob_fee_per_byte(excess_blob_gas),
+                calc_blob_gasprice(excess_blob_gas)
+            );
+        }
+    }
+
+    #[test]
+    fn blob_gas_used_per_blob() {
+        assert_eq!(blob_gas_used(0), 0);
+        assert_eq!(blob_gas_used(1), 131_072);
+        assert_eq!(
+            blob_gas_used(MAX_BLOBS_PER_BLOCK),
+            crate::constants::eip4844::MAX_DATA_GAS_PER_BLOCK
+        );
+    }
+
+    #[test]
+    fn excess_blob_gas_after_block() {
+        for (parent_excess_blob_gas, blob_count) in [(0, 0), (0, 6), (10_000_000, 1), (0, 3)] {
+            assert_eq!(
+                calculate_excess_blob_gas_after_block(parent_excess_blob_gas, blob_count),
+                calculate_excess_blob_gas(
+                    parent_excess_blob_gas,
+                    DATA_GAS_PER_BLOB * blob_count as u64
+                )
+            );
+        }
+
+        // a block with the maximum of 6 blobs exceeds the target of 3 blobs by 3 blobs
+        assert_eq!(calculate_excess_blob_gas_after_block(0, 6), 3 * DATA_GAS_PER_BLOB);
+    }
+
+    #[cfg(feature = "c-kzg")]
+    #[test]
+    fn sidecar_of_non_blob_transaction() {
+        use alloy_eips::eip4844::env_settings::EnvKzgSettings;
+
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy::default()),
+            Signature::default(),
+        );
+        assert!(matches!(
+            validate_blob_transaction_sidecar(
+                &tx,
+                &BlobTransactionSidecar::default(),
+                EnvKzgSettings::Default.get()
+            ),
+            Err(BlobTransactionValidationError::NotBlobTransaction(0))
+        ));
+    }
+
+    #[cfg(feature = "c-kzg")]
+    #[test]
+    fn sidecar_validation_errors() {
+        use alloy_eips::eip4844::env_settings::EnvKzgSettings;
+
+        let settings = EnvKzgSettings::Default.get();
+        let sidecar = BlobTransactionSidecar::default();
+        assert_eq!(validate_blob_sidecar(&[], &sidecar, settings), Ok(()));
+        assert_eq!(
+            validate_blob_sidecar(&[B256::ZERO], &sidecar, settings),
+            Err(BlobSidecarValidationError::CommitmentCountMismatch { expected: 1, got: 0 })
+        );
+
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Default::default()],
+            commitments: vec![Default::default()],
+            proofs: vec![Default::default()],
+        };
+        let got = kzg_to_versioned_hash(sidecar.commitments[0].as_slice());
+        assert_eq!(
+            validate_blob_sidecar(&[B256::ZERO], &sidecar, settings),
+            Err(BlobSidecarValidationError::VersionedHashMismatch {
+                index: 0,
+                expected: B256::ZERO,
+                got
+            })
+        );
+        assert_eq!(
+            validate_blob_sidecar(&[got], &sidecar, settings),
+            Err(BlobSidecarValidationError::KzgProofInvalid { index: 0 })
+        );
+    }
+}
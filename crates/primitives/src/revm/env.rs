@@ -3,9 +3,12 @@ use crate::{
     revm_primitives::{BlockEnv, Env, TxEnv},
     Address, Bytes, Header, Transaction, TransactionSignedEcRecovered, TxKind, B256, U256,
 };
-use reth_chainspec::{Chain, ChainSpec};
+use reth_chainspec::{Chain, ChainHardforks, ChainSpec, EthereumHardforks};
 
-use alloy_eips::{eip4788::BEACON_ROOTS_ADDRESS, eip7002::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS};
+use alloy_eips::{
+    eip2935::HISTORY_STORAGE_ADDRESS, eip4788::BEACON_ROOTS_ADDRESS,
+    eip7002::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+};
 #[cfg(feature = "optimism")]
 use revm_primitives::OptimismFields;
 
@@ -13,14 +16,39 @@ use revm_primitives::OptimismFields;
 use alloc::vec::Vec;
 
 /// Fill block environment from Block.
+///
+/// If Cancun is active, the blob excess gas and price are always set, defaulting to zero excess
+/// blob gas if the header does not include it.
 pub fn fill_block_env(
     block_env: &mut BlockEnv,
     chain_spec: &ChainSpec,
     header: &Header,
     after_merge: bool,
 ) {
-    let coinbase = block_coinbase(chain_spec, header, after_merge);
+    fill_block_env_with_hardforks(
+        block_env,
+        chain_spec.chain,
+        &chain_spec.hardforks,
+        header,
+        after_merge,
+    )
+}
+
+/// Same as [`fill_block_env`], for the given chain and hardforks instead of a [`ChainSpec`].
+pub fn fill_block_env_with_hardforks(
+    block_env: &mut BlockEnv,
+    chain: Chain,
+    hardforks: &ChainHardforks,
+    header: &Header,
+    after_merge: bool,
+) {
+    let coinbase = block_coinbase_for_chain(chain, header, after_merge);
     fill_block_env_with_coinbase(block_env, header, after_merge, coinbase);
+
+    if header.excess_blob_gas.is_none() && hardforks.is_cancun_active_at_timestamp(header.timestamp)
+    {
+        block_env.set_blob_excess_gas_and_price(0);
+    }
 }
 
 /// Fill block environment with coinbase.
@@ -44,7 +72,9 @@ pub fn fill_block_env_with_coinbase(
     block_env.basefee = U256::from(header.base_fee_per_gas.unwrap_or_default());
     block_env.gas_limit = U256::from(header.gas_limit);
 
-    // EIP-4844 excess blob gas of this block, introduced in Cancun
+    // EIP-4844 excess blob gas of this block, introduced in Cancun. `Some(0)` is a valid Cancun
+    // value and must still be set: it yields the minimum blob gas price of 1, whereas skipping the
+    // setter would leave the block without a blob gas price.
     if let Some(excess_blob_gas) = header.excess_blob_gas {
         block_env.set_blob_excess_gas_and_price(excess_blob_gas);
     }
@@ -52,6 +82,11 @@ pub fn fill_block_env_with_coinbase(
 
 /// Return the coinbase address for the given header and chain spec.
 pub fn block_coinbase(chain_spec: &ChainSpec, header: &Header, after_merge: bool) -> Address {
+    block_coinbase_for_chain(chain_spec.chain, header, after_merge)
+}
+
+/// Return the coinbase address for the given header on the given chain.
+pub fn block_coinbase_for_chain(chain: Chain, header: &Header, after_merge: bool) -> Address {
     // Clique consensus fills the EXTRA_SEAL (last 65 bytes) of the extra data with the
     // signer's signature.
     //
@@ -63,7 +98,7 @@ pub fn block_coinbase(chain_spec: &ChainSpec, header: &Header, after_merge: bool
     // * `EXTRA_SEAL`: Fixed number of extra-data suffix bytes reserved for signer seal.
     //   * 65 bytes fixed as signatures are based on the standard `secp256k1` curve.
     //   * Filled with zeros on genesis block.
-    if chain_spec.chain == Chain::goerli() && !after_merge && header.number > 0 {
+    if chain == Chain::goerli() && !after_merge && header.number > 0 {
         recover_header_signer(header).unwrap_or_else(|err| {
             panic!(
                 "Failed to recover goerli Clique Consensus signer from header ({}, {}) using extradata {}: {:?}",
@@ -145,6 +180,26 @@ pub fn fill_tx_env_with_beacon_root_contract_call(env: &mut Env, parent_beacon_b
         alloy_eips::eip4788::SYSTEM_ADDRESS,
         BEACON_ROOTS_ADDRESS,
         parent_beacon_block_root.0.into(),
+        None,
+    );
+}
+
+/// Fill transaction environment with the EIP-2935 history storage contract message data.
+///
+/// This requirement for the history storage contract call defined by
+/// [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935) is:
+///
+/// At the start of processing any execution block where `block.timestamp >= FORK_TIMESTAMP` (i.e.
+/// before processing any transactions), call [`HISTORY_STORAGE_ADDRESS`] as
+/// [`SYSTEM_ADDRESS`](alloy_eips::eip4788::SYSTEM_ADDRESS) with the 32-byte input of
+/// `header.parent_hash`. This will store the parent block hash in the history storage contract.
+pub fn fill_tx_env_with_blockhashes_contract_call(env: &mut Env, parent_block_hash: B256) {
+    fill_tx_env_with_system_contract_call(
+        env,
+        alloy_eips::eip4788::SYSTEM_ADDRESS,
+        HISTORY_STORAGE_ADDRESS,
+        parent_block_hash.0.into(),
+        None,
     );
 }
 
@@ -162,6 +217,7 @@ pub fn fill_tx_env_with_withdrawal_requests_contract_call(env: &mut Env) {
         alloy_eips::eip7002::SYSTEM_ADDRESS,
         WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
         Bytes::new(),
+        None,
     );
 }
 
@@ -174,11 +230,15 @@ pub fn fill_tx_env_with_withdrawal_requests_contract_call(env: &mut Env) {
 ///  * the call does not follow the EIP-1559 burn semantics - no value should be transferred as part
 ///    of the call
 ///  * if no code exists at the provided address, the call will fail silently
+///
+/// All current system calls transfer no value and pass `None` as `value`, which is treated as
+/// zero.
 fn fill_tx_env_with_system_contract_call(
     env: &mut Env,
     caller: Address,
     contract: Address,
     data: Bytes,
+    value: Option<U256>,
 ) {
     env.tx = TxEnv {
         caller,
@@ -186,7 +246,7 @@ fn fill_tx_env_with_system_contract_call(
         // Explicitly set nonce to None so revm does not do any nonce checks
         nonce: None,
         gas_limit: 30_000_000,
-        value: U256::ZERO,
+        value: value.unwrap_or_default(),
         data,
         // Setting the gas price to zero enforces that no value is transferred as part of the call,
         // and that the call will not count against the block's gas limit
@@ -359,7 +419,7 @@ pub fn fill_op_tx_env<T: AsRef<Transaction>>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_chainspec::GOERLI;
+    use reth_chainspec::{GOERLI, MAINNET};
 
     #[test]
     fn test_recover_genesis_goerli_signer() {
@@ -369,4 +429,30 @@ mod tests {
         let block_coinbase = block_coinbase(&chain_spec, &header, false);
         assert_eq!(block_coinbase, header.beneficiary);
     }
+
+    #[test]
+    fn test_fill_block_env_sets_blob_gas_after_cancun() {
+        let mut header = Header { timestamp: 1_720_000_000, ..Default::default() };
+
+        let mut block_env = BlockEnv { blob_excess_gas_and_price: None, ..Default::default() };
+        fill_block_env(&mut block_env, &MAINNET, &header, true);
+        assert_eq!(block_env.get_blob_excess_gas(), Some(0));
+        assert_eq!(block_env.get_blob_gasprice(), Some(1));
+
+        // before Cancun the blob excess gas is left unset
+        header.timestamp = 1_700_000_000;
+        let mut block_env = BlockEnv { blob_excess_gas_and_price: None, ..Default::default() };
+        fill_block_env(&mut block_env, &MAINNET, &header, true);
+        assert_eq!(block_env.get_blob_excess_gas(), None);
+    }
+
+    #[test]
+    fn test_fill_block_env_sets_zero_excess_blob_gas() {
+        let header = Header { excess_blob_gas: Some(0), ..Default::default() };
+
+        let mut block_env = BlockEnv { blob_excess_gas_and_price: None, ..Default::default() };
+        fill_block_env_with_coinbase(&mut block_env, &header, true, header.beneficiary);
+        assert_eq!(block_env.get_blob_excess_gas(), Some(0));
+        assert_eq!(block_env.get_blob_gasprice(), Some(1));
+    }
 }
@@ -705,6 +705,12 @@ impl Default for Transaction {
     }
 }
 
+impl AsRef<Self> for Transaction {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
 impl Encodable for Transaction {
     /// This encodes the transaction _without_ the signature, and is only suitable for creating a
     /// hash intended for signing.
@@ -4,9 +4,13 @@ use crate::{
 };
 use rayon::prelude::*;
 use reth_db::tables;
-use reth_db_api::database::Database;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    transaction::DbTxMut,
+};
 use reth_provider::{DatabaseProviderRW, TransactionsProvider};
-use reth_prune_types::{PruneMode, PruneProgress, PruneSegment};
+use reth_prune_types::{PruneInterruptReason, PruneMode, PruneProgress, PruneSegment};
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
@@ -43,10 +47,17 @@ impl<DB: Database> Segment<DB> for TransactionLookup {
             }
         }
         .into_inner();
-        let tx_range = start..=
-            Some(end)
-                .min(input.limiter.deleted_entries_limit_left().map(|left| start + left as u64 - 1))
-                .unwrap();
+        // Every pruned transaction deletes one entry from both `TransactionHashNumbers` and
+        // `TransactionHashLocations`, so the range is capped at half of the entries left.
+        let tx_range_limit = input.limiter.deleted_entries_limit_left().map(|left| left / 2);
+        if tx_range_limit == Some(0) {
+            return Ok(PruneOutput::not_done(
+                PruneInterruptReason::DeletedEntriesLimitReached,
+                input.previous_checkpoint.map(|checkpoint| checkpoint.into()),
+            ))
+        }
+        let tx_range =
+            start..=Some(end).min(tx_range_limit.map(|limit| start + limit as u64 - 1)).unwrap();
         let tx_range_end = *tx_range.end();
 
         // Retrieve transactions in the range and calculate their hashes in parallel
@@ -67,13 +78,30 @@ impl<DB: Database> Segment<DB> for TransactionLookup {
         let mut limiter = input.limiter;
 
         let mut last_pruned_transaction = None;
-        let (pruned, done) = provider.prune_table_with_iterator::<tables::TransactionHashNumbers>(
-            hashes,
-            &mut limiter,
-            |row| {
-                last_pruned_transaction = Some(last_pruned_transaction.unwrap_or(row.1).max(row.1))
-            },
-        )?;
+        let mut pruned_hashes = Vec::new();
+        let (mut pruned, done) = provider
+            .prune_table_with_iterator::<tables::TransactionHashNumbers>(
+                hashes,
+                &mut limiter,
+                |row| {
+                    last_pruned_transaction =
+                        Some(last_pruned_transaction.unwrap_or(row.1).max(row.1));
+                    pruned_hashes.push(row.0);
+                },
+            )?;
+
+        // Prune the secondary index along with the pruned transaction lookup entries. These
+        // deletions are not interrupted by the limiter, so that both tables stay consistent, but
+        // they are still accounted for.
+        let mut location_cursor =
+            provider.tx_ref().cursor_write::<tables::TransactionHashLocations>()?;
+        for hash in pruned_hashes {
+            if location_cursor.seek_exact(hash)?.is_some() {
+                location_cursor.delete_current()?;
+                limiter.increment_deleted_entries_count();
+                pruned += 1;
+            }
+        }
 
         let done = done && tx_range_end == end;
         trace!(target: "pruner", %pruned, %done, "Pruned transaction lookup");
@@ -111,6 +139,7 @@ mod tests {
         Itertools,
     };
     use reth_db::tables;
+    use reth_db_api::{models::StoredTransactionLocation, transaction::DbTxMut};
     use reth_provider::PruneCheckpointReader;
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneLimiter, PruneMode, PruneProgress, PruneSegment,
@@ -128,12 +157,27 @@ mod tests {
         db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
 
         let mut tx_hash_numbers = Vec::new();
+        let mut tx_hash_locations = Vec::new();
         for block in &blocks {
-            for transaction in &block.body {
+            for (tx_index, transaction) in block.body.iter().enumerate() {
                 tx_hash_numbers.push((transaction.hash, tx_hash_numbers.len() as u64));
+                tx_hash_locations.push((
+                    transaction.hash,
+                    StoredTransactionLocation {
+                        block_number: block.number,
+                        tx_index: tx_index as u64,
+                    },
+                ));
             }
         }
         db.insert_tx_hash_numbers(tx_hash_numbers.clone()).expect("insert tx hash numbers");
+        db.commit(|tx| {
+            for (hash, location) in tx_hash_locations {
+                tx.put::<tables::TransactionHashLocations>(hash, location)?;
+            }
+            Ok(())
+        })
+        .expect("insert tx hash locations");
 
         assert_eq!(
             db.table::<tables::Transactions>().unwrap().len(),
@@ -143,6 +187,10 @@ mod tests {
             db.table::<tables::Transactions>().unwrap().len(),
             db.table::<tables::TransactionHashNumbers>().unwrap().len()
         );
+        assert_eq!(
+            db.table::<tables::Transactions>().unwrap().len(),
+            db.table::<tables::TransactionHashLocations>().unwrap().len()
+        );
 
         let test_prune = |to_block: BlockNumber, expected_result: (PruneProgress, usize)| {
             let prune_mode = PruneMode::Before(to_block);
@@ -175,8 +223,9 @@ mod tests {
                 .map(|block| block.body.len())
                 .sum::<usize>()
                 .min(
+                    // every transaction deletes an entry from both lookup tables
                     next_tx_number_to_prune as usize +
-                        input.limiter.deleted_entries_limit().unwrap(),
+                        input.limiter.deleted_entries_limit().unwrap() / 2,
                 )
                 .sub(1);
 
@@ -219,6 +268,10 @@ mod tests {
                 db.table::<tables::TransactionHashNumbers>().unwrap().len(),
                 tx_hash_numbers.len() - (last_pruned_tx_number + 1)
             );
+            assert_eq!(
+                db.table::<tables::TransactionHashLocations>().unwrap().len(),
+                tx_hash_numbers.len() - (last_pruned_tx_number + 1)
+            );
             assert_eq!(
                 db.factory
                     .provider()
@@ -237,7 +290,50 @@ mod tests {
             6,
             (PruneProgress::HasMoreData(PruneInterruptReason::DeletedEntriesLimitReached), 10),
         );
-        test_prune(6, (PruneProgress::Finished, 2));
-        test_prune(10, (PruneProgress::Finished, 8));
+        test_prune(
+            6,
+            (PruneProgress::HasMoreData(PruneInterruptReason::DeletedEntriesLimitReached), 10),
+        );
+        test_prune(6, (PruneProgress::Finished, 4));
+        test_prune(
+            10,
+            (PruneProgress::HasMoreData(PruneInterruptReason::DeletedEntriesLimitReached), 10),
+        );
+        test_prune(10, (PruneProgress::Finished, 6));
+    }
+
+    #[test]
+    fn prune_respects_limit_below_one_transaction() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=2, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let tx_hash_numbers = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .enumerate()
+            .map(|(tx_number, transaction)| (transaction.hash, tx_number as u64))
+            .collect::<Vec<_>>();
+        db.insert_tx_hash_numbers(tx_hash_numbers.clone()).expect("insert tx hash numbers");
+
+        // pruning a single transaction deletes two entries
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block: 2,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(1),
+        };
+        let provider = db.factory.provider_rw().unwrap();
+        let result = TransactionLookup::new(PruneMode::Before(2)).prune(&provider, input).unwrap();
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            result,
+            PruneOutput::not_done(PruneInterruptReason::DeletedEntriesLimitReached, None)
+        );
+        assert_eq!(
+            db.table::<tables::TransactionHashNumbers>().unwrap().len(),
+            tx_hash_numbers.len()
+        );
     }
 }
@@ -9,7 +9,8 @@ use thiserror::Error;
 pub enum PruneSegment {
     /// Prune segment responsible for the `TransactionSenders` table.
     SenderRecovery,
-    /// Prune segment responsible for the `TransactionHashNumbers` table.
+    /// Prune segment responsible for the `TransactionHashNumbers` and
+    /// `TransactionHashLocations` tables.
     TransactionLookup,
     /// Prune segment responsible for all rows in `Receipts` table.
     Receipts,
@@ -8,10 +8,10 @@ use reth_consensus_common::calc;
 use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_primitives::{
     revm::env::{
-        fill_tx_env_with_beacon_root_contract_call,
+        fill_tx_env_with_beacon_root_contract_call, fill_tx_env_with_blockhashes_contract_call,
         fill_tx_env_with_withdrawal_requests_contract_call,
     },
-    Address, Block, Request, Withdrawal, Withdrawals, B256, U256,
+    Address, Block, Bytes, Header, Request, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_storage_errors::provider::ProviderError;
 use revm::{
@@ -139,11 +139,89 @@ fn eip2935_block_hash_slot<DB: Database<Error: Into<ProviderError>>>(
     Ok((slot, EvmStorageSlot::new_changed(current_hash, block_hash.into())))
 }
 
+/// The outcome of a system contract call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemCallResult {
+    /// The output of the call, or the revert data if the call reverted.
+    pub output: Bytes,
+    /// The gas used by the call.
+    pub gas_used: u64,
+    /// Whether the call succeeded, a reverted or halted call does not fail the block.
+    pub success: bool,
+}
+
+impl From<ExecutionResult> for SystemCallResult {
+    fn from(result: ExecutionResult) -> Self {
+        Self {
+            success: result.is_success(),
+            gas_used: result.gas_used(),
+            output: result.into_output().unwrap_or_default(),
+        }
+    }
+}
+
+/// Applies the pre-block call to the [EIP-2935] history storage contract, using the given block,
+/// [`ChainSpec`], EVM.
+///
+/// This is the system call alternative to [`apply_blockhashes_update`], which writes the parent
+/// block hash to the contract storage directly.
+///
+/// If Prague is not activated, or the block is the genesis block, then this is a no-op, no state
+/// changes are made and `None` is returned. Otherwise, the [`SystemCallResult`] of the call is
+/// returned.
+///
+/// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+#[inline]
+pub fn apply_blockhashes_contract_call<EXT, DB: Database + DatabaseCommit>(
+    chain_spec: &ChainSpec,
+    block_timestamp: u64,
+    block_number: u64,
+    parent_block_hash: B256,
+    evm: &mut Evm<'_, EXT, DB>,
+) -> Result<Option<SystemCallResult>, BlockExecutionError>
+where
+    DB::Error: core::fmt::Display,
+{
+    // If Prague is not activated or this is the genesis block, no hashes are added.
+    if !chain_spec.is_prague_active_at_timestamp(block_timestamp) || block_number == 0 {
+        return Ok(None)
+    }
+
+    // get previous env
+    let previous_env = Box::new(evm.context.env().clone());
+
+    // modify env for pre block call
+    fill_tx_env_with_blockhashes_contract_call(&mut evm.context.evm.env, parent_block_hash);
+
+    let ResultAndState { result, mut state } = match evm.transact() {
+        Ok(res) => res,
+        Err(e) => {
+            evm.context.evm.env = previous_env;
+            return Err(BlockValidationError::BlockHashContractCall {
+                parent_block_hash: Box::new(parent_block_hash),
+                message: e.to_string(),
+            }
+            .into())
+        }
+    };
+
+    state.remove(&alloy_eips::eip4788::SYSTEM_ADDRESS);
+    state.remove(&evm.block().coinbase);
+
+    evm.context.evm.db.commit(state);
+
+    // re-set the previous env
+    evm.context.evm.env = previous_env;
+
+    Ok(Some(result.into()))
+}
+
 /// Applies the pre-block call to the [EIP-4788] beacon block root contract, using the given block,
 /// [`ChainSpec`], EVM.
 ///
-/// If Cancun is not activated or the block is the genesis block, then this is a no-op, and no
-/// state changes are made.
+/// If Cancun is not activated or the block is the genesis block, then this is a no-op, no state
+/// changes are made and `None` is returned. Otherwise, the [`SystemCallResult`] of the call is
+/// returned.
 ///
 /// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
 #[inline]
@@ -153,12 +231,12 @@ pub fn apply_beacon_root_contract_call<EXT, DB: Database + DatabaseCommit>(
     block_number: u64,
     parent_beacon_block_root: Option<B256>,
     evm: &mut Evm<'_, EXT, DB>,
-) -> Result<(), BlockExecutionError>
+) -> Result<Option<SystemCallResult>, BlockExecutionError>
 where
     DB::Error: core::fmt::Display,
 {
     if !chain_spec.is_cancun_active_at_timestamp(block_timestamp) {
-        return Ok(())
+        return Ok(None)
     }
 
     let parent_beacon_block_root =
@@ -173,7 +251,7 @@ where
             }
             .into())
         }
-        return Ok(())
+        return Ok(None)
     }
 
     // get previous env
@@ -182,8 +260,8 @@ where
     // modify env for pre block call
     fill_tx_env_with_beacon_root_contract_call(&mut evm.context.evm.env, parent_beacon_block_root);
 
-    let mut state = match evm.transact() {
-        Ok(res) => res.state,
+    let ResultAndState { result, mut state } = match evm.transact() {
+        Ok(res) => res,
         Err(e) => {
             evm.context.evm.env = previous_env;
             return Err(BlockValidationError::BeaconRootContractCall {
@@ -202,7 +280,47 @@ where
     // re-set the previous env
     evm.context.evm.env = previous_env;
 
-    Ok(())
+    Ok(Some(result.into()))
+}
+
+/// Applies all pre-block system calls that are active for the given block header.
+///
+/// This applies, in order:
+///  - the [EIP-4788] beacon block root contract call, see [`apply_beacon_root_contract_call`].
+///  - the [EIP-2935] historical block hashes update, see [`apply_blockhashes_update`].
+///
+/// Each of them is a no-op if the corresponding hardfork is not active at the header's timestamp.
+///
+/// The parent beacon block root is passed separately, so that executors can use a root that is not
+/// part of the header.
+///
+/// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+/// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+#[inline]
+pub fn apply_pre_block_calls<EXT, DB>(
+    chain_spec: &ChainSpec,
+    header: &Header,
+    parent_beacon_block_root: Option<B256>,
+    evm: &mut Evm<'_, EXT, DB>,
+) -> Result<(), BlockExecutionError>
+where
+    DB: Database<Error: Into<ProviderError>> + DatabaseCommit,
+    DB::Error: core::fmt::Display,
+{
+    apply_beacon_root_contract_call(
+        chain_spec,
+        header.timestamp,
+        header.number,
+        parent_beacon_block_root,
+        evm,
+    )?;
+    apply_blockhashes_update(
+        evm.db_mut(),
+        chain_spec,
+        header.timestamp,
+        header.number,
+        header.parent_hash,
+    )
 }
 
 /// Returns a map of addresses to their balance increments if the Shanghai hardfork is active at the
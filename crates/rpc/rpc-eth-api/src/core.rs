@@ -373,7 +373,7 @@ where
     /// Handler for: `eth_getBlockByHash`
     async fn block_by_hash(&self, hash: B256, full: bool) -> RpcResult<Option<RichBlock>> {
         trace!(target: "rpc::eth", ?hash, ?full, "Serving eth_getBlockByHash");
-        Ok(EthBlocks::rpc_block(self, hash.into(), full).await?)
+        Ok(EthBlocks::rpc_block(self, hash.into(), full.into()).await?)
     }
 
     /// Handler for: `eth_getBlockByNumber`
@@ -383,7 +383,7 @@ where
         full: bool,
     ) -> RpcResult<Option<RichBlock>> {
         trace!(target: "rpc::eth", ?number, ?full, "Serving eth_getBlockByNumber");
-        Ok(EthBlocks::rpc_block(self, number.into(), full).await?)
+        Ok(EthBlocks::rpc_block(self, number.into(), full.into()).await?)
     }
 
     /// Handler for: `eth_getBlockTransactionCountByHash`
@@ -404,7 +404,7 @@ where
     /// Handler for: `eth_getUncleCountByBlockHash`
     async fn block_uncles_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?hash, "Serving eth_getUncleCountByBlockHash");
-        Ok(EthBlocks::ommers(self, hash.into())?.map(|ommers| U256::from(ommers.len())))
+        Ok(EthBlocks::uncle_count(self, hash.into()).await?.map(U256::from))
     }
 
     /// Handler for: `eth_getUncleCountByBlockNumber`
@@ -413,7 +413,7 @@ where
         number: BlockNumberOrTag,
     ) -> RpcResult<Option<U256>> {
         trace!(target: "rpc::eth", ?number, "Serving eth_getUncleCountByBlockNumber");
-        Ok(EthBlocks::ommers(self, number.into())?.map(|ommers| U256::from(ommers.len())))
+        Ok(EthBlocks::uncle_count(self, number.into()).await?.map(U256::from))
     }
 
     /// Handler for: `eth_getBlockReceipts`
@@ -1,25 +1,78 @@
 //! Database access for `eth_` block RPC methods. Loads block and receipt data w.r.t. network.
 
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 
-use futures::Future;
-use reth_primitives::{BlockId, Receipt, SealedBlock, SealedBlockWithSenders, TransactionMeta};
+use alloy_rlp::Encodable;
+use futures::{Future, StreamExt};
+use reth_errors::{ConsensusError, RethError};
+use reth_primitives::{
+    proofs::calculate_withdrawals_root, Address, BlockId, BlockNumber, Bytes, GotExpected, Receipt,
+    SealedBlock, SealedBlockWithSenders, TxType, EMPTY_OMMER_ROOT_HASH,
+};
 use reth_provider::{BlockIdReader, BlockReader, BlockReaderIdExt, HeaderProvider};
-use reth_rpc_eth_types::{EthApiError, EthResult, EthStateCache, ReceiptBuilder};
-use reth_rpc_types::{AnyTransactionReceipt, Header, Index, RichBlock};
-use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
+use reth_rpc_eth_types::{build_block_receipts, EthApiError, EthResult, EthStateCache};
+use reth_rpc_types::{AnyTransactionReceipt, BlockTransactionsKind, Header, Index, RichBlock};
+use reth_rpc_types_compat::block::{from_block, from_primitive_with_hash, uncle_block_from_header};
+use tracing::{debug_span, warn, Instrument};
 
 use super::{LoadPendingBlock, LoadReceipt, SpawnBlocking};
 
+/// Result of [`EthBlocks::load_block_and_receipts`], a block and its shared receipts.
+pub type BlockAndReceiptsResult<E = EthApiError> =
+    Result<Option<(SealedBlock, Arc<Vec<Receipt>>)>, E>;
+
+/// Result of [`EthBlocks::block_receipts`], the receipts of a block.
+pub type BlockReceiptsResult<E = EthApiError> = Result<Option<Vec<AnyTransactionReceipt>>, E>;
+
+/// Level of detail of the transactions of a block returned by [`EthBlocks::rpc_block`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransactionDetails {
+    /// Only include the hashes of the transactions.
+    #[default]
+    Hashes,
+    /// Include the full transaction objects.
+    Full,
+}
+
+impl From<bool> for TransactionDetails {
+    fn from(full: bool) -> Self {
+        if full {
+            Self::Full
+        } else {
+            Self::Hashes
+        }
+    }
+}
+
+impl From<TransactionDetails> for BlockTransactionsKind {
+    fn from(details: TransactionDetails) -> Self {
+        match details {
+            TransactionDetails::Hashes => Self::Hashes,
+            TransactionDetails::Full => Self::Full,
+        }
+    }
+}
+
 /// Block related functions for the [`EthApiServer`](crate::EthApiServer) trait in the
 /// `eth_` namespace.
 pub trait EthBlocks: LoadBlock {
+    /// Maximum number of blocks [`block_receipts_range`](EthBlocks::block_receipts_range) returns
+    /// the receipts of in a single call.
+    const MAX_BLOCK_RECEIPTS_RANGE: u64 = 1_000;
+
+    /// Maximum number of blocks [`block_receipts_range`](EthBlocks::block_receipts_range) loads
+    /// concurrently.
+    const BLOCK_RECEIPTS_RANGE_CONCURRENCY: usize = 16;
+
     /// Returns a handle for reading data from disk.
     ///
     /// Data access in default (L1) trait method implementations.
     fn provider(&self) -> impl HeaderProvider;
 
     /// Returns the block header for the given block id.
+    ///
+    /// The header includes all post-merge fields, e.g. the withdrawals root and the blob gas
+    /// fields, as well as the total difficulty.
     fn rpc_block_header(
         &self,
         block_id: BlockId,
@@ -27,17 +80,31 @@ pub trait EthBlocks: LoadBlock {
     where
         Self: LoadPendingBlock + SpawnBlocking,
     {
-        async move { Ok(self.rpc_block(block_id, false).await?.map(|block| block.inner.header)) }
+        async move {
+            let Some(block) = self.block(block_id).await? else { return Ok(None) };
+            let total_difficulty = EthBlocks::provider(self)
+                .header_td_by_number(block.number)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+
+            // converting the sealed header directly populates all header fields, including the
+            // post-merge and cancun fields, without converting the block body
+            let mut header = from_primitive_with_hash(block.header);
+            header.total_difficulty = Some(total_difficulty);
+            Ok(Some(header))
+        }
     }
 
     /// Returns the populated rpc block object for the given block id.
     ///
-    /// If `full` is true, the block object will contain all transaction objects, otherwise it will
-    /// only contain the transaction hashes.
+    /// The [`TransactionDetails`] determine whether the block object contains all transaction
+    /// objects or only the transaction hashes.
+    ///
+    /// Returns an error if the withdrawals of the block do not match the header's withdrawals
+    /// root.
     fn rpc_block(
         &self,
         block_id: BlockId,
-        full: bool,
+        details: TransactionDetails,
     ) -> impl Future<Output = EthResult<Option<RichBlock>>> + Send
     where
         Self: LoadPendingBlock + SpawnBlocking,
@@ -48,11 +115,29 @@ pub trait EthBlocks: LoadBlock {
                 None => return Ok(None),
             };
             let block_hash = block.hash();
+
+            // the block hash only commits to the withdrawals root, so corrupted withdrawals would
+            // otherwise go unnoticed
+            let withdrawals_root = block
+                .withdrawals
+                .as_ref()
+                .map(|withdrawals| calculate_withdrawals_root(withdrawals));
+            if withdrawals_root != block.withdrawals_root {
+                return Err(RethError::from(ConsensusError::BodyWithdrawalsRootDiff(
+                    GotExpected {
+                        got: withdrawals_root.unwrap_or_default(),
+                        expected: block.withdrawals_root.unwrap_or_default(),
+                    }
+                    .into(),
+                ))
+                .into())
+            }
+
             let total_difficulty = EthBlocks::provider(self)
                 .header_td_by_number(block.number)?
                 .ok_or(EthApiError::UnknownBlockNumber)?;
             let block =
-                from_block(block.unseal(), total_difficulty, full.into(), Some(block_hash))?;
+                from_block(block.unseal(), total_difficulty, details.into(), Some(block_hash))?;
             Ok(Some(block.into()))
         }
     }
@@ -79,65 +164,168 @@ pub trait EthBlocks: LoadBlock {
         }
     }
 
+    /// Returns the number transactions in the given block, or in the latest block if no block id
+    /// is given.
+    ///
+    /// Returns `None` if the block does not exist
+    fn block_transaction_count_or_latest(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> impl Future<Output = EthResult<Option<usize>>> + Send {
+        self.block_transaction_count(block_id.unwrap_or_else(BlockId::latest))
+    }
+
+    /// Returns the size of the RLP encoded block in bytes.
+    ///
+    /// Returns `None` if the block does not exist
+    fn block_size(&self, block_id: BlockId) -> impl Future<Output = EthResult<Option<u64>>> + Send {
+        async move { Ok(self.block(block_id).await?.map(|block| block.length() as u64)) }
+    }
+
+    /// Returns the author of the given block, i.e. the beneficiary of the block header.
+    ///
+    /// Returns `None` if the block does not exist
+    fn block_author(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = EthResult<Option<Address>>> + Send {
+        async move {
+            if block_id.is_pending() {
+                // Pending block can be fetched directly without need for caching
+                return Ok(LoadBlock::provider(self)
+                    .pending_block()?
+                    .map(|block| block.beneficiary))
+            }
+
+            let block_hash = match LoadBlock::provider(self).block_hash_for_id(block_id)? {
+                Some(block_hash) => block_hash,
+                None => return Ok(None),
+            };
+
+            Ok(self.cache().get_sealed_block(block_hash).await?.map(|block| block.beneficiary))
+        }
+    }
+
+    /// Returns the raw `extraData` field of the given block's header.
+    ///
+    /// Returns `None` if the block does not exist
+    fn block_extra_data(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = EthResult<Option<Bytes>>> + Send {
+        async move {
+            if block_id.is_pending() {
+                // Pending block can be fetched directly without need for caching
+                return Ok(LoadBlock::provider(self)
+                    .pending_block()?
+                    .map(|block| block.header.unseal().extra_data))
+            }
+
+            let block_hash = match LoadBlock::provider(self).block_hash_for_id(block_id)? {
+                Some(block_hash) => block_hash,
+                None => return Ok(None),
+            };
+
+            Ok(self
+                .cache()
+                .get_sealed_block(block_hash)
+                .await?
+                .map(|block| block.header.unseal().extra_data))
+        }
+    }
+
     /// Helper function for `eth_getBlockReceipts`.
     ///
     /// Returns all transaction receipts in block, or `None` if block wasn't found.
-    fn block_receipts(
+    fn block_receipts(&self, block_id: BlockId) -> impl Future<Output = BlockReceiptsResult> + Send
+    where
+        Self: LoadReceipt,
+    {
+        async move {
+            if let Some((block, receipts)) = self.load_block_and_receipts(block_id).await? {
+                return build_block_receipts(block, &receipts, |_| true).map(Some)
+            }
+
+            Ok(None)
+        }
+    }
+
+    /// Returns the receipts of all transactions of the given [`TxType`] in the block.
+    ///
+    /// Returns `None` if block wasn't found.
+    fn block_receipts_by_tx_type(
         &self,
         block_id: BlockId,
-    ) -> impl Future<Output = EthResult<Option<Vec<AnyTransactionReceipt>>>> + Send
+        tx_type: TxType,
+    ) -> impl Future<Output = BlockReceiptsResult> + Send
     where
         Self: LoadReceipt,
     {
         async move {
             if let Some((block, receipts)) = self.load_block_and_receipts(block_id).await? {
-                let block_number = block.number;
-                let base_fee = block.base_fee_per_gas;
-                let block_hash = block.hash();
-                let excess_blob_gas = block.excess_blob_gas;
-                let timestamp = block.timestamp;
-                let block = block.unseal();
-
-                let receipts = block
-                    .body
-                    .into_iter()
-                    .zip(receipts.iter())
-                    .enumerate()
-                    .map(|(idx, (tx, receipt))| {
-                        let meta = TransactionMeta {
-                            tx_hash: tx.hash,
-                            index: idx as u64,
-                            block_hash,
-                            block_number,
-                            base_fee,
-                            excess_blob_gas,
-                            timestamp,
-                        };
-
-                        ReceiptBuilder::new(&tx, meta, receipt, &receipts)
-                            .map(|builder| builder.build())
-                    })
-                    .collect::<EthResult<Vec<_>>>();
-                return receipts.map(Some)
+                return build_block_receipts(block, &receipts, |tx| tx.tx_type() == tx_type)
+                    .map(Some)
             }
 
             Ok(None)
         }
     }
 
+    /// Returns the receipts of all blocks in the given range, in order.
+    ///
+    /// At most [`BLOCK_RECEIPTS_RANGE_CONCURRENCY`](EthBlocks::BLOCK_RECEIPTS_RANGE_CONCURRENCY)
+    /// blocks are loaded concurrently. Each block has its own result, like
+    /// [`block_receipts`](EthBlocks::block_receipts), so a block that failed to load doesn't fail
+    /// the entire range.
+    ///
+    /// Returns an error if the range spans more than
+    /// [`MAX_BLOCK_RECEIPTS_RANGE`](EthBlocks::MAX_BLOCK_RECEIPTS_RANGE) blocks.
+    fn block_receipts_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Future<Output = EthResult<Vec<(BlockId, BlockReceiptsResult)>>> + Send
+    where
+        Self: LoadReceipt,
+    {
+        async move {
+            if range.end().saturating_sub(*range.start()) >= Self::MAX_BLOCK_RECEIPTS_RANGE {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block range exceeds the maximum of {} blocks",
+                    Self::MAX_BLOCK_RECEIPTS_RANGE
+                )))
+            }
+
+            let receipts = futures::stream::iter(range.map(BlockId::from))
+                .map(|block_id| async move { (block_id, self.block_receipts(block_id).await) })
+                .buffered(Self::BLOCK_RECEIPTS_RANGE_CONCURRENCY)
+                .collect()
+                .await;
+
+            Ok(receipts)
+        }
+    }
+
     /// Helper method that loads a bock and all its receipts.
+    ///
+    /// The receipts of the pending block are wrapped the same way as the cached receipts of
+    /// canonical blocks, so callers handle both alike. If there is no pending block from the CL,
+    /// the locally built pending block is used, whose receipts are cached along with it.
     fn load_block_and_receipts(
         &self,
         block_id: BlockId,
-    ) -> impl Future<Output = EthResult<Option<(SealedBlock, Arc<Vec<Receipt>>)>>> + Send
+    ) -> impl Future<Output = BlockAndReceiptsResult> + Send
     where
         Self: LoadReceipt,
     {
         async move {
             if block_id.is_pending() {
-                return Ok(LoadBlock::provider(self)
-                    .pending_block_and_receipts()?
-                    .map(|(sb, receipts)| (sb, Arc::new(receipts))))
+                if let Some((block, receipts)) =
+                    LoadBlock::provider(self).pending_block_and_receipts()?
+                {
+                    return Ok(Some((block, Arc::new(receipts))))
+                }
+                let pending = self.local_pending_block_and_receipts().await?;
+                return Ok(pending.map(|(block, receipts)| (block.block, receipts)))
             }
 
             if let Some(block_hash) = LoadBlock::provider(self).block_hash_for_id(block_id)? {
@@ -155,6 +343,35 @@ pub trait EthBlocks: LoadBlock {
         Ok(LoadBlock::provider(self).ommers_by_id(block_id)?)
     }
 
+    /// Returns the number of uncles in the given block.
+    ///
+    /// This only reads the header if the block has no uncles, instead of loading all uncle
+    /// headers.
+    ///
+    /// Returns `None` if the block does not exist.
+    fn uncle_count(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = EthResult<Option<usize>>> + Send {
+        async move {
+            if block_id.is_pending() {
+                // Pending block can be fetched directly without need for caching
+                return Ok(LoadBlock::provider(self)
+                    .pending_block()?
+                    .map(|block| block.ommers.len()))
+            }
+
+            let Some(header) = LoadBlock::provider(self).header_by_id(block_id)? else {
+                return Ok(None)
+            };
+            if header.ommers_hash == EMPTY_OMMER_ROOT_HASH {
+                return Ok(Some(0))
+            }
+
+            Ok(LoadBlock::provider(self).ommers_by_id(block_id)?.map(|ommers| ommers.len()))
+        }
+    }
+
     /// Returns uncle block at given index in given block.
     ///
     /// Returns `None` if index out of range.
@@ -168,7 +385,10 @@ pub trait EthBlocks: LoadBlock {
                 // Pending block can be fetched directly without need for caching
                 LoadBlock::provider(self).pending_block()?.map(|block| block.ommers)
             } else {
-                LoadBlock::provider(self).ommers_by_id(block_id)?
+                match LoadBlock::provider(self).block_hash_for_id(block_id)? {
+                    Some(block_hash) => self.cache().get_block_ommers(block_hash).await?,
+                    None => None,
+                }
             }
             .unwrap_or_default();
 
@@ -184,6 +404,13 @@ pub trait EthBlocks: LoadBlock {
 ///
 /// Behaviour shared by several `eth_` RPC methods, not exclusive to `eth_` blocks RPC methods.
 pub trait LoadBlock: LoadPendingBlock + SpawnBlocking {
+    /// Number of times [`block_with_senders`](LoadBlock::block_with_senders) resolves the block id
+    /// again if the block it resolved to is gone, e.g. because it was reorged out in the meantime.
+    ///
+    /// Only applies to block numbers and tags: a block hash always resolves to the same block, so
+    /// it is never retried.
+    const BLOCK_WITH_SENDERS_RETRIES: usize = 1;
+
     // Returns a handle for reading data from disk.
     ///
     /// Data access in default (L1) trait method implementations.
@@ -192,6 +419,8 @@ pub trait LoadBlock: LoadPendingBlock + SpawnBlocking {
     /// Returns a handle for reading data from memory.
     ///
     /// Data access in default (L1) trait method implementations.
+    ///
+    /// The returned [`EthStateCache`] can be cloned to access the cache from background tasks.
     fn cache(&self) -> &EthStateCache;
 
     /// Returns the block object for the given block id.
@@ -223,12 +452,41 @@ pub trait LoadBlock: LoadPendingBlock + SpawnBlocking {
                 }
             }
 
-            let block_hash = match LoadPendingBlock::provider(self).block_hash_for_id(block_id)? {
-                Some(block_hash) => block_hash,
-                None => return Ok(None),
+            // a block hash always resolves to the same block, so retrying is pointless
+            let max_retries = if matches!(block_id, BlockId::Hash(_)) {
+                0
+            } else {
+                Self::BLOCK_WITH_SENDERS_RETRIES
             };
+            let mut retry = 0;
+            loop {
+                let block_hash =
+                    match LoadPendingBlock::provider(self).block_hash_for_id(block_id)? {
+                        Some(block_hash) => block_hash,
+                        None => return Ok(None),
+                    };
 
-            Ok(self.cache().get_sealed_block_with_senders(block_hash).await?)
+                let block = self
+                    .cache()
+                    .get_sealed_block_with_senders(block_hash)
+                    .instrument(debug_span!(
+                        target: "rpc::eth",
+                        "load_block_with_senders",
+                        %block_hash,
+                        retry
+                    ))
+                    .await?;
+                if block.is_some() || retry >= max_retries {
+                    return Ok(block)
+                }
+                retry += 1;
+                warn!(
+                    target: "rpc::eth",
+                    %block_hash,
+                    retry,
+                    "Block not found in cache, possible reorg, retrying"
+                );
+            }
         }
     }
 }
@@ -18,6 +18,7 @@ use reth_rpc_eth_types::{
     revm_utils::{
         apply_state_overrides, build_call_evm_env, caller_gas_allowance,
         cap_tx_gas_limit_with_caller_allowance, get_precompiles, prepare_call_env,
+        recovered_tx_env,
     },
     EthApiError, EthResult, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
@@ -121,7 +122,7 @@ pub trait EthCall: Call + LoadPendingBlock {
                         let env = EnvWithHandlerCfg::new_with_cfg_env(
                             cfg.clone(),
                             block_env.clone(),
-                            Call::evm_config(&this).tx_env(&tx),
+                            recovered_tx_env(Call::evm_config(&this), &tx)?,
                         );
                         let (res, _) = this.transact(&mut db, env)?;
                         db.commit(res.state);
@@ -426,7 +427,7 @@ pub trait Call: LoadState + SpawnBlocking {
                 let env = EnvWithHandlerCfg::new_with_cfg_env(
                     cfg,
                     block_env,
-                    Call::evm_config(&this).tx_env(&tx),
+                    recovered_tx_env(Call::evm_config(&this), &tx)?,
                 );
 
                 let (res, _) = this.transact(&mut db, env)?;
@@ -26,7 +26,9 @@ pub mod state;
 pub mod trace;
 pub mod transaction;
 
-pub use block::{EthBlocks, LoadBlock};
+pub use block::{
+    BlockAndReceiptsResult, BlockReceiptsResult, EthBlocks, LoadBlock, TransactionDetails,
+};
 pub use blocking_task::SpawnBlocking;
 pub use call::{Call, EthCall};
 pub use fee::{EthFees, LoadFee};
@@ -1,7 +1,10 @@
 //! Loads a pending block from database. Helper trait for `eth_` block, transaction, call and trace
 //! RPC methods.
 
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures::Future;
 use reth_chainspec::EthereumHardforks;
@@ -26,6 +29,7 @@ use reth_revm::{
 };
 use reth_rpc_eth_types::{
     pending_block::{pre_block_beacon_root_contract_call, pre_block_blockhashes_update},
+    revm_utils::recovered_tx_env,
     EthApiError, EthResult, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin,
 };
 use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
@@ -116,13 +120,31 @@ pub trait LoadPendingBlock {
     fn local_pending_block(
         &self,
     ) -> impl Future<Output = EthResult<Option<SealedBlockWithSenders>>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move { Ok(self.local_pending_block_and_receipts().await?.map(|(block, _)| block)) }
+    }
+
+    /// Returns the locally built pending block and its receipts.
+    ///
+    /// The receipts are cached together with the block in [`PendingBlock`], so requesting them
+    /// again before the block expires does not execute the pending transactions again. They are
+    /// deliberately not stored in the `EthStateCache` under a sentinel hash: the pending block is
+    /// replaced whenever it expires or a new block is mined, and only [`PendingBlock`] tracks
+    /// that, while an entry in the hash keyed cache would outlive the block it belongs to.
+    fn local_pending_block_and_receipts(
+        &self,
+    ) -> impl Future<Output = EthResult<Option<(SealedBlockWithSenders, Arc<Vec<Receipt>>)>>> + Send
     where
         Self: SpawnBlocking,
     {
         async move {
             let pending = self.pending_block_env_and_cfg()?;
             if pending.origin.is_actual_pending() {
-                return Ok(pending.origin.into_actual_pending())
+                let Some(block) = pending.origin.into_actual_pending() else { return Ok(None) };
+                let receipts = self.provider().receipts_by_block_id(block.hash().into())?;
+                return Ok(receipts.map(|receipts| (block, Arc::new(receipts))))
             }
 
             let mut lock = self.pending_block().lock().await;
@@ -136,12 +158,12 @@ pub trait LoadPendingBlock {
                     pending.origin.header().hash() == pending_block.block.parent_hash &&
                     now <= pending_block.expires_at
                 {
-                    return Ok(Some(pending_block.block.clone()))
+                    return Ok(Some((pending_block.block.clone(), pending_block.receipts.clone())))
                 }
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let pending_block = match self
+            let (pending_block, receipts) = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
                     this.build_block(pending)
@@ -155,10 +177,15 @@ pub trait LoadPendingBlock {
                 }
             };
 
+            let receipts = Arc::new(receipts);
             let now = Instant::now();
-            *lock = Some(PendingBlock::new(pending_block.clone(), now + Duration::from_secs(1)));
+            *lock = Some(PendingBlock::new(
+                pending_block.clone(),
+                receipts.clone(),
+                now + Duration::from_secs(1),
+            ));
 
-            Ok(Some(pending_block))
+            Ok(Some((pending_block, receipts)))
         }
     }
 
@@ -193,13 +220,17 @@ pub trait LoadPendingBlock {
         execution_outcome.receipts_root_slow(block_number).expect("Block is present")
     }
 
-    /// Builds a pending block using the configured provider and pool.
+    /// Builds a pending block using the configured provider and pool, and returns it together with
+    /// its receipts.
     ///
     /// If the origin is the actual pending block, the block is built with withdrawals.
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
-    fn build_block(&self, env: PendingBlockEnv) -> EthResult<SealedBlockWithSenders> {
+    fn build_block(
+        &self,
+        env: PendingBlockEnv,
+    ) -> EthResult<(SealedBlockWithSenders, Vec<Receipt>)> {
         let PendingBlockEnv { cfg, block_env, origin } = env;
 
         let parent_hash = origin.build_target_hash();
@@ -294,7 +325,7 @@ pub trait LoadPendingBlock {
             let env = Env::boxed(
                 cfg.cfg_env.clone(),
                 block_env.clone(),
-                Self::evm_config(self).tx_env(&tx),
+                recovered_tx_env(Self::evm_config(self), &tx)?,
             );
 
             let mut evm = revm::Evm::builder().with_env(env).with_db(&mut db).build();
@@ -421,6 +452,8 @@ pub trait LoadPendingBlock {
 
         // seal the block
         let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests };
-        Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
+        let receipts =
+            execution_outcome.receipts.receipt_vec.into_iter().flatten().flatten().collect();
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, receipts))
     }
 }
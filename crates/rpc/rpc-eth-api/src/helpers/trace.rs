@@ -6,10 +6,11 @@ use reth_primitives::B256;
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+    revm_utils::recovered_tx_env,
     EthApiError, EthResult,
 };
 use reth_rpc_types::{BlockId, TransactionInfo};
-use revm::{db::CacheDB, Database, DatabaseCommit, GetInspector, Inspector};
+use revm::{db::CacheDB, Database, DatabaseCommit, Evm, GetInspector, Inspector};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
 use revm_primitives::{EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState};
 
@@ -60,6 +61,22 @@ pub trait Trace: LoadState {
         Ok((res, env, db))
     }
 
+    /// Returns a new EVM with the given database and environment, that uses a [`TracingInspector`]
+    /// configured by the config as its inspector.
+    ///
+    /// The inspector can be taken back after execution with `evm.into_context().external`.
+    fn evm_with_tracer<'a, DB>(
+        &'a self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        config: TracingInspectorConfig,
+    ) -> Evm<'a, TracingInspector, DB>
+    where
+        DB: Database + 'a,
+    {
+        self.evm_config().evm_with_env_and_inspector(db, env, TracingInspector::new(config))
+    }
+
     /// Executes the transaction on top of the given [`BlockId`] with a tracer configured by the
     /// config.
     ///
@@ -80,9 +97,9 @@ pub trait Trace: LoadState {
     {
         self.with_state_at_block(at, |state| {
             let mut db = CacheDB::new(StateProviderDatabase::new(state));
-            let mut inspector = TracingInspector::new(config);
-            let (res, _) = self.inspect(&mut db, env, &mut inspector)?;
-            f(inspector, res)
+            let mut evm = self.evm_with_tracer(&mut db, env, config);
+            let res = evm.transact()?;
+            f(evm.into_context().external, res)
         })
     }
 
@@ -199,7 +216,7 @@ pub trait Trace: LoadState {
                 let env = EnvWithHandlerCfg::new_with_cfg_env(
                     cfg,
                     block_env,
-                    Call::evm_config(&this).tx_env(&tx),
+                    recovered_tx_env(Call::evm_config(&this), &tx)?,
                 );
                 let (res, _) =
                     this.inspect(StateCacheDbRefMutWrapper(&mut db), env, &mut inspector)?;
@@ -311,7 +328,7 @@ pub trait Trace: LoadState {
                             block_number: Some(block_number),
                             base_fee: Some(base_fee),
                         };
-                        let tx_env = Trace::evm_config(&this).tx_env(&tx);
+                        let tx_env = recovered_tx_env(Trace::evm_config(&this), &tx);
                         (tx_info, tx_env)
                     })
                     .peekable();
@@ -322,6 +339,7 @@ pub trait Trace: LoadState {
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
 
                 while let Some((tx_info, tx)) = transactions.next() {
+                    let tx = tx?;
                     let env =
                         EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx);
 
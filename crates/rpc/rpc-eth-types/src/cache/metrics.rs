@@ -14,4 +14,6 @@ pub(crate) struct CacheMetrics {
     pub(crate) hits_total: Counter,
     /// The number of cache misses.
     pub(crate) misses_total: Counter,
+    /// The number of evicted entries.
+    pub(crate) evictions_total: Counter,
 }
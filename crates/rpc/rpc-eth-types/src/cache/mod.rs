@@ -5,8 +5,8 @@ use reth_errors::{ProviderError, ProviderResult};
 use reth_evm::ConfigureEvm;
 use reth_execution_types::Chain;
 use reth_primitives::{
-    Block, BlockHashOrNumber, BlockWithSenders, Receipt, SealedBlock, SealedBlockWithSenders,
-    TransactionSigned, TransactionSignedEcRecovered, B256,
+    Block, BlockHashOrNumber, BlockWithSenders, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, TransactionSigned, TransactionSignedEcRecovered, B256,
 };
 use reth_provider::{
     BlockReader, CanonStateNotification, EvmEnvProvider, StateProviderFactory, TransactionVariant,
@@ -59,10 +59,27 @@ type ReceiptsLruCache<L> =
 type EnvLruCache<L> =
     MultiConsumerLruCache<B256, (CfgEnvWithHandlerCfg, BlockEnv), L, EnvResponseSender>;
 
+/// The listener that is invoked with the kind of cache and the block hash of an evicted entry.
+type CacheEvictionListener = Arc<dyn Fn(CacheKind, B256) + Send + Sync>;
+
+/// The LRU caches managed by the [`EthStateCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheKind {
+    /// The cache for full blocks with senders.
+    Blocks,
+    /// The cache for the receipts of a block.
+    Receipts,
+    /// The cache for revm environments.
+    EvmEnv,
+}
+
 /// Provides async access to cached eth data
 ///
 /// This is the frontend for the async caching service which manages cached data on a different
 /// task.
+///
+/// The handle is cheap to clone and all clones share the same cache, so a clone can be moved into
+/// a background task, e.g. to pre-warm the cache for upcoming blocks.
 #[derive(Debug, Clone)]
 pub struct EthStateCache {
     to_service: UnboundedSender<CacheAction>,
@@ -164,6 +181,13 @@ impl EthStateCache {
         Ok(self.get_block(block_hash).await?.map(|block| block.seal(block_hash)))
     }
 
+    /// Requests the ommers of the [Block]
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_block_ommers(&self, block_hash: B256) -> ProviderResult<Option<Vec<Header>>> {
+        Ok(self.get_block(block_hash).await?.map(|block| block.ommers))
+    }
+
     /// Requests the transactions of the [Block]
     ///
     /// Returns `None` if the block does not exist.
@@ -249,6 +273,21 @@ impl EthStateCache {
         Ok(block.zip(receipts))
     }
 
+    /// Fetches both receipts and block for all of the given block hashes.
+    ///
+    /// All requests are sent to the cache service at once, so cache hits are answered directly
+    /// and concurrent requests for the same block are only fetched from the database once. The
+    /// results are returned in the order of the given hashes.
+    pub async fn get_blocks_and_receipts_batch(
+        &self,
+        block_hashes: Vec<B256>,
+    ) -> Vec<ProviderResult<Option<(SealedBlock, Arc<Vec<Receipt>>)>>> {
+        futures::future::join_all(
+            block_hashes.into_iter().map(|block_hash| self.get_block_and_receipts(block_hash)),
+        )
+        .await
+    }
+
     /// Requests the evm env config for the block hash.
     ///
     /// Returns an error if the corresponding header (required for populating the envs) was not
@@ -261,6 +300,20 @@ impl EthStateCache {
         let _ = self.to_service.send(CacheAction::GetEnv { block_hash, response_tx });
         rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
     }
+
+    /// Registers a listener that is invoked with the kind of cache and the block hash of every
+    /// entry that is evicted from one of the LRU caches, e.g. to count evictions.
+    ///
+    /// The listener is installed on the cache service task and replaces any previously registered
+    /// listener. It is invoked on the service task, so it should not block.
+    pub fn with_eviction_listener(
+        self,
+        listener: impl Fn(CacheKind, B256) + Send + Sync + 'static,
+    ) -> Self {
+        let _ =
+            self.to_service.send(CacheAction::SetEvictionListener { listener: Arc::new(listener) });
+        self
+    }
 }
 
 /// A task than manages caches for data required by the `eth` rpc implementation.
@@ -391,6 +444,20 @@ where
         }
     }
 
+    fn set_eviction_listener(&mut self, listener: CacheEvictionListener) {
+        let blocks_listener = listener.clone();
+        self.full_block_cache.set_eviction_listener(Box::new(move |block_hash, _| {
+            blocks_listener(CacheKind::Blocks, block_hash)
+        }));
+        let receipts_listener = listener.clone();
+        self.receipts_cache.set_eviction_listener(Box::new(move |block_hash, _| {
+            receipts_listener(CacheKind::Receipts, block_hash)
+        }));
+        self.evm_env_cache.set_eviction_listener(Box::new(move |block_hash, _| {
+            listener(CacheKind::EvmEnv, block_hash)
+        }));
+    }
+
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
         self.receipts_cache.update_cached_metrics();
@@ -574,6 +641,9 @@ where
                                 );
                             }
                         }
+                        CacheAction::SetEvictionListener { listener } => {
+                            this.set_eviction_listener(listener);
+                        }
                         CacheAction::RemoveReorgedChain { chain_change } => {
                             for block in chain_change.blocks {
                                 this.on_reorg_block(block.hash(), Ok(Some(block.unseal())));
@@ -607,6 +677,7 @@ enum CacheAction {
     EnvResult { block_hash: B256, res: Box<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>> },
     CacheNewCanonicalChain { chain_change: ChainChange },
     RemoveReorgedChain { chain_change: ChainChange },
+    SetEvictionListener { listener: CacheEvictionListener },
 }
 
 struct BlockReceipts {
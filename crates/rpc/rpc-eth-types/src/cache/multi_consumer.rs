@@ -11,6 +11,9 @@ use schnellru::{ByLength, Limiter, LruMap};
 
 use super::metrics::CacheMetrics;
 
+/// A callback that is invoked with every entry that is evicted from a [`MultiConsumerLruCache`].
+pub type EvictionListener<K, V> = Box<dyn Fn(K, V) + Send + Sync>;
+
 /// A multi-consumer LRU cache.
 pub struct MultiConsumerLruCache<K, V, L, S>
 where
@@ -23,6 +26,8 @@ where
     queued: HashMap<K, Vec<S>>,
     /// Cache metrics
     metrics: CacheMetrics,
+    /// Optional listener for evicted entries.
+    eviction_listener: Option<EvictionListener<K, V>>,
 }
 
 impl<K, V, L, S> Debug for MultiConsumerLruCache<K, V, L, S>
@@ -35,6 +40,7 @@ where
             .field("cache_length", &self.cache.len())
             .field("cache_memory_usage", &self.cache.memory_usage())
             .field("queued_length", &self.queued.len())
+            .field("has_eviction_listener", &self.eviction_listener.is_some())
             .finish()
     }
 }
@@ -84,8 +90,16 @@ where
         entry
     }
 
+    /// Sets the listener that is invoked with every entry that is evicted from the cache.
+    pub fn set_eviction_listener(&mut self, listener: EvictionListener<K, V>) {
+        self.eviction_listener = Some(listener);
+    }
+
     /// Inserts a new element into the map.
     ///
+    /// If the new element would exceed the limit of the cache, the least recently used element is
+    /// evicted first and passed to the eviction listener, if any.
+    ///
     /// Can fail if the element is rejected by the limiter or if we fail to grow an empty map.
     ///
     /// See [`Schnellru::insert`](LruMap::insert) for more info.
@@ -93,6 +107,16 @@ where
     where
         L::KeyToInsert<'a>: Hash + PartialEq<K>,
     {
+        if self.cache.peek(&key).is_none() &&
+            self.cache.limiter().is_over_the_limit(self.cache.len() + 1)
+        {
+            if let Some((evicted_key, evicted_value)) = self.cache.pop_oldest() {
+                self.metrics.evictions_total.increment(1);
+                if let Some(listener) = &self.eviction_listener {
+                    listener(evicted_key, evicted_value);
+                }
+            }
+        }
         self.cache.insert(key, value)
     }
 
@@ -113,6 +137,7 @@ where
             cache: LruMap::new(ByLength::new(max_len)),
             queued: Default::default(),
             metrics: CacheMetrics::new_with_labels(&[("cache", cache_id.to_string())]),
+            eviction_listener: None,
         }
     }
 }
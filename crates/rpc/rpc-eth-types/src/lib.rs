@@ -22,7 +22,7 @@ pub mod utils;
 
 pub use cache::{
     config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
-    EthStateCache,
+    CacheKind, EthStateCache,
 };
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
@@ -30,5 +30,5 @@ pub use gas_oracle::{GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracl
 pub use id_provider::EthSubscriptionIdProvider;
 pub use logs_utils::EthFilterError;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
-pub use receipt::ReceiptBuilder;
+pub use receipt::{build_block_receipts, ReceiptBuilder};
 pub use transaction::TransactionSource;
@@ -2,11 +2,13 @@
 //!
 //! Types used in block building.
 
-use std::{fmt, time::Instant};
+use std::{fmt, sync::Arc, time::Instant};
 
 use derive_more::Constructor;
 use reth_chainspec::ChainSpec;
-use reth_primitives::{BlockId, BlockNumberOrTag, SealedBlockWithSenders, SealedHeader, B256};
+use reth_primitives::{
+    BlockId, BlockNumberOrTag, Receipt, SealedBlockWithSenders, SealedHeader, B256,
+};
 use reth_provider::ProviderError;
 use reth_revm::state_change::{apply_beacon_root_contract_call, apply_blockhashes_update};
 use revm_primitives::{
@@ -63,7 +65,9 @@ where
         parent_beacon_block_root,
         &mut evm_pre_block,
     )
-    .map_err(|err| EthApiError::Internal(err.into()))
+    .map_err(|err| EthApiError::Internal(err.into()))?;
+
+    Ok(())
 }
 
 /// Apply the [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935) pre block state transitions.
@@ -157,6 +161,8 @@ impl PendingBlockEnvOrigin {
 pub struct PendingBlock {
     /// The cached pending block
     pub block: SealedBlockWithSenders,
+    /// The receipts of the cached pending block
+    pub receipts: Arc<Vec<Receipt>>,
     /// Timestamp when the pending block is considered outdated
     pub expires_at: Instant,
 }
@@ -1,6 +1,6 @@
 //! RPC receipt response builder, extends a layer one receipt with layer two data.
 
-use reth_primitives::{Address, Receipt, TransactionMeta, TransactionSigned, TxKind};
+use reth_primitives::{Address, Receipt, SealedBlock, TransactionMeta, TransactionSigned, TxKind};
 use reth_rpc_types::{
     AnyReceiptEnvelope, AnyTransactionReceipt, Log, OtherFields, ReceiptWithBloom,
     TransactionReceipt, WithOtherFields,
@@ -124,3 +124,92 @@ impl ReceiptBuilder {
         res
     }
 }
+
+/// Builds the receipt responses of the transactions in the block that match the given predicate.
+///
+/// This requires _all_ receipts of the block, in order. The transaction index of a receipt is the
+/// position of its transaction in the block, regardless of which transactions are filtered out.
+pub fn build_block_receipts(
+    block: SealedBlock,
+    receipts: &[Receipt],
+    mut predicate: impl FnMut(&TransactionSigned) -> bool,
+) -> EthResult<Vec<AnyTransactionReceipt>> {
+    let block_number = block.number;
+    let base_fee = block.base_fee_per_gas;
+    let block_hash = block.hash();
+    let excess_blob_gas = block.excess_blob_gas;
+    let timestamp = block.timestamp;
+    let block = block.unseal();
+
+    block
+        .body
+        .into_iter()
+        .zip(receipts.iter())
+        .enumerate()
+        .filter(|(_, (tx, _))| predicate(tx))
+        .map(|(idx, (tx, receipt))| {
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: idx as u64,
+                block_hash,
+                block_number,
+                base_fee,
+                excess_blob_gas,
+                timestamp,
+            };
+
+            ReceiptBuilder::new(&tx, meta, receipt, receipts).map(|builder| builder.build())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, Transaction, TxEip1559, TxEip2930, TxEip4844, TxLegacy, TxType};
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+
+    #[test]
+    fn build_block_receipts_by_tx_type() {
+        let mut rng = generators::rng();
+        let body = vec![
+            Transaction::Legacy(TxLegacy::default()),
+            Transaction::Eip2930(TxEip2930::default()),
+            Transaction::Eip1559(TxEip1559::default()),
+            Transaction::Eip4844(TxEip4844::default()),
+        ]
+        .into_iter()
+        .map(|tx| sign_tx_with_random_key_pair(&mut rng, tx))
+        .collect::<Vec<_>>();
+        let receipts = body
+            .iter()
+            .enumerate()
+            .map(|(idx, tx)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                cumulative_gas_used: 21_000 * (idx as u64 + 1),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let block =
+            SealedBlock { header: Header::default().seal_slow(), body, ..Default::default() };
+
+        // all receipts
+        let all = build_block_receipts(block.clone(), &receipts, |_| true).unwrap();
+        assert_eq!(all.len(), 4);
+
+        for (idx, tx_type) in [TxType::Legacy, TxType::Eip2930, TxType::Eip1559, TxType::Eip4844]
+            .into_iter()
+            .enumerate()
+        {
+            let filtered =
+                build_block_receipts(block.clone(), &receipts, |tx| tx.tx_type() == tx_type)
+                    .unwrap();
+            assert_eq!(filtered.len(), 1);
+            assert_eq!(filtered[0].transaction_hash, block.body[idx].hash());
+            // the index is the position in the block, not in the filtered receipts
+            assert_eq!(filtered[0].transaction_index, Some(idx as u64));
+            assert_eq!(filtered[0].gas_used, 21_000);
+        }
+    }
+}
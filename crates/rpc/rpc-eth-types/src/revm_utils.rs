@@ -2,7 +2,8 @@
 
 use std::cmp::min;
 
-use reth_primitives::{Address, TxKind, B256, U256};
+use reth_evm::ConfigureEvmEnv;
+use reth_primitives::{Address, TransactionSignedEcRecovered, TxKind, B256, U256};
 use reth_rpc_types::{
     state::{AccountOverride, EvmOverrides, StateOverride},
     BlockOverrides, TransactionRequest,
@@ -28,6 +29,17 @@ pub fn get_precompiles(spec_id: SpecId) -> impl IntoIterator<Item = Address> {
     Precompiles::new(spec).addresses().copied().map(Address::from)
 }
 
+/// Returns the [`TxEnv`] of the recovered transaction, as configured by the given
+/// [`ConfigureEvmEnv`].
+///
+/// A transaction rejected by the configuration is returned as [`EthApiError::EvmCustom`].
+pub fn recovered_tx_env<EvmConfig: ConfigureEvmEnv>(
+    evm_config: &EvmConfig,
+    transaction: &TransactionSignedEcRecovered,
+) -> EthResult<TxEnv> {
+    evm_config.tx_env(transaction).map_err(|err| EthApiError::EvmCustom(err.to_string()))
+}
+
 /// Prepares the [`EnvWithHandlerCfg`] for execution.
 ///
 /// Does not commit any changes to the underlying database.
@@ -16,7 +16,10 @@ use reth_provider::{
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_eth_api::helpers::{Call, EthApiSpec, EthTransactions, TraceExt};
-use reth_rpc_eth_types::{revm_utils::prepare_call_env, EthApiError, EthResult, StateCacheDb};
+use reth_rpc_eth_types::{
+    revm_utils::{prepare_call_env, recovered_tx_env},
+    EthApiError, EthResult, StateCacheDb,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
     state::EvmOverrides,
@@ -105,7 +108,7 @@ where
                         env: Env::boxed(
                             cfg.cfg_env.clone(),
                             block_env.clone(),
-                            Call::evm_config(this.eth_api()).tx_env(&tx),
+                            recovered_tx_env(Call::evm_config(this.eth_api()), &tx)?,
                         ),
                         handler_cfg: cfg.handler_cfg,
                     };
@@ -248,7 +251,7 @@ where
                     env: Env::boxed(
                         cfg.cfg_env.clone(),
                         block_env,
-                        Call::evm_config(this.eth_api()).tx_env(&tx),
+                        recovered_tx_env(Call::evm_config(this.eth_api()), &tx)?,
                     ),
                     handler_cfg: cfg.handler_cfg,
                 };
@@ -481,7 +484,7 @@ where
                             env: Env::boxed(
                                 cfg.cfg_env.clone(),
                                 block_env.clone(),
-                                Call::evm_config(this.eth_api()).tx_env(&tx),
+                                recovered_tx_env(Call::evm_config(this.eth_api()), &tx)?,
                             ),
                             handler_cfg: cfg.handler_cfg,
                         };
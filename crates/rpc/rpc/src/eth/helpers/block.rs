@@ -32,3 +32,56 @@ where
         self.inner.cache()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, BlockId};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_eth_types::{FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle};
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    use super::*;
+
+    type TestEthApi = EthApi<MockEthProvider, TestPool, (), EthEvmConfig>;
+
+    fn build_test_eth_api(provider: MockEthProvider) -> TestEthApi {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_range_returns_result_per_block() {
+        let eth_api = build_test_eth_api(MockEthProvider::default());
+
+        let receipts = eth_api.block_receipts_range(5..=9).await.unwrap();
+        assert_eq!(
+            receipts.iter().map(|(block_id, _)| *block_id).collect::<Vec<_>>(),
+            (5..=9).map(BlockId::from).collect::<Vec<_>>()
+        );
+        // none of the blocks are known
+        assert!(receipts.iter().all(|(_, receipts)| matches!(receipts, Ok(None))));
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_range_is_capped() {
+        let eth_api = build_test_eth_api(MockEthProvider::default());
+        let max = <TestEthApi as EthBlocks>::MAX_BLOCK_RECEIPTS_RANGE;
+
+        assert_eq!(eth_api.block_receipts_range(0..=max - 1).await.unwrap().len(), max as usize);
+        assert!(eth_api.block_receipts_range(0..=max).await.is_err());
+    }
+}
@@ -38,3 +38,52 @@ where
         self.inner.evm_config()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, Block, BlockNumberOrTag, B256};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_eth_api::helpers::EthBlocks;
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pending_block_receipts_are_cached() {
+        let mock_provider = MockEthProvider::default();
+        mock_provider.add_block(B256::random(), Block::default());
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            mock_provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+
+        // the first call builds the pending block locally
+        let receipts = eth_api.block_receipts(BlockNumberOrTag::Pending.into()).await.unwrap();
+        assert!(receipts.is_some());
+        let cached = eth_api.pending_block().lock().await.as_ref().unwrap().receipts.clone();
+
+        // the second call is served from the cached pending block instead of building it again
+        let receipts = eth_api.block_receipts(BlockNumberOrTag::Pending.into()).await.unwrap();
+        assert!(receipts.is_some());
+        let lock = eth_api.pending_block().lock().await;
+        assert!(Arc::ptr_eq(&cached, &lock.as_ref().unwrap().receipts));
+    }
+}
@@ -14,7 +14,7 @@ use reth_rpc_api::TraceApiServer;
 use reth_rpc_eth_api::helpers::{Call, TraceExt};
 use reth_rpc_eth_types::{
     error::{EthApiError, EthResult},
-    revm_utils::prepare_call_env,
+    revm_utils::{prepare_call_env, recovered_tx_env},
     utils::recover_raw_transaction,
 };
 use reth_rpc_types::{
@@ -118,7 +118,7 @@ where
         let env = EnvWithHandlerCfg::new_with_cfg_env(
             cfg,
             block,
-            Call::evm_config(self.eth_api()).tx_env(&tx.into_ecrecovered_transaction()),
+            recovered_tx_env(Call::evm_config(self.eth_api()), &tx.into_ecrecovered_transaction())?,
         );
 
         let config = TracingInspectorConfig::from_parity_config(&trace_types);
@@ -111,7 +111,7 @@ impl<DB: Database> Stage<DB> for IndexStorageHistoryStage {
                 |AddressStorageKey((address, storage_key)), highest_block_number| {
                     StorageShardedKey::new(address, storage_key, highest_block_number)
                 },
-                |(key, value)| (key.block_number(), AddressStorageKey((key.address(), value.key))),
+                |(key, value)| (key.block_number(), (key.address(), value.key).into()),
                 &self.etl_config,
             )?;
 
@@ -124,7 +124,7 @@ impl<DB: Database> Stage<DB> for IndexStorageHistoryStage {
                 StorageShardedKey::new(address, storage_key, highest_block_number)
             },
             StorageShardedKey::decode,
-            |key| AddressStorageKey((key.address, key.sharded_key.key)),
+            |key| (key.address, key.sharded_key.key).into(),
         )?;
 
         Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
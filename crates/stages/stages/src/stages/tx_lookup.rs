@@ -4,6 +4,7 @@ use reth_db::{tables, RawKey, RawValue};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
+    models::StoredTransactionLocation,
     transaction::{DbTx, DbTxMut},
 };
 use reth_etl::Collector;
@@ -27,6 +28,9 @@ use tracing::*;
 /// [`tables::TransactionHashNumbers`] This is used for looking up changesets via the transaction
 /// hash.
 ///
+/// The block number and the index of each transaction in its block are also written to the
+/// [`tables::TransactionHashLocations`] secondary index.
+///
 /// It uses [`reth_etl::Collector`] to collect all entries before finally writing them to disk.
 #[derive(Debug, Clone)]
 pub struct TransactionLookupStage {
@@ -107,6 +111,8 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         // 500MB temporary files
         let mut hash_collector: Collector<TxHash, TxNumber> =
             Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut location_collector: Collector<TxHash, StoredTransactionLocation> =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
 
         info!(
             target: "sync::stages::transaction_lookup",
@@ -122,7 +128,26 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
 
             info!(target: "sync::stages::transaction_lookup", ?tx_range, "Calculating transaction hashes");
 
+            let block_indices = provider
+                .tx_ref()
+                .cursor_read::<tables::BlockBodyIndices>()?
+                .walk_range(block_range)?
+                .collect::<Result<Vec<_>, _>>()?;
+
             for (key, value) in provider.transaction_hashes_by_range(tx_range)? {
+                // Find the first block that ends after the transaction, empty blocks are skipped.
+                let position =
+                    block_indices.partition_point(|(_, indices)| indices.next_tx_num() <= value);
+                let (block_number, indices) = block_indices
+                    .get(position)
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(end_block))?;
+                location_collector.insert(
+                    key,
+                    StoredTransactionLocation {
+                        block_number: *block_number,
+                        tx_index: value - indices.first_tx_num(),
+                    },
+                )?;
                 hash_collector.insert(key, value)?;
             }
 
@@ -164,6 +189,22 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
                     "Transaction hashes inserted"
                 );
 
+                let append_only =
+                    provider.count_entries::<tables::TransactionHashLocations>()?.is_zero();
+                let mut location_cursor = provider
+                    .tx_ref()
+                    .cursor_write::<tables::RawTable<tables::TransactionHashLocations>>()?;
+                for hash_to_location in location_collector.iter()? {
+                    let (hash, location) = hash_to_location?;
+                    let key = RawKey::<TxHash>::from_vec(hash);
+                    let value = RawValue::<StoredTransactionLocation>::from_vec(location);
+                    if append_only {
+                        location_cursor.append(key, value)?
+                    } else {
+                        location_cursor.insert(key, value)?
+                    }
+                }
+
                 break
             }
         }
@@ -187,6 +228,7 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         // Cursors to unwind tx hash to number
         let mut body_cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
         let mut tx_hash_number_cursor = tx.cursor_write::<tables::TransactionHashNumbers>()?;
+        let mut tx_hash_location_cursor = tx.cursor_write::<tables::TransactionHashLocations>()?;
         let static_file_provider = provider.static_file_provider();
         let mut rev_walker = body_cursor.walk_back(Some(*range.end()))?;
         while let Some((number, body)) = rev_walker.next().transpose()? {
@@ -201,6 +243,9 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
                     if tx_hash_number_cursor.seek_exact(transaction.hash())?.is_some() {
                         tx_hash_number_cursor.delete_current()?;
                     }
+                    if tx_hash_location_cursor.seek_exact(transaction.hash())?.is_some() {
+                        tx_hash_location_cursor.delete_current()?;
+                    }
                 }
             }
         }
@@ -521,11 +566,20 @@ mod tests {
                         provider.tx_ref().cursor_read::<tables::BlockBodyIndices>()?;
                     body_cursor.seek_exact(start_block)?;
 
-                    while let Some((_, body)) = body_cursor.next()? {
+                    while let Some((number, body)) = body_cursor.next()? {
                         for tx_id in body.tx_num_range() {
                             let transaction =
                                 provider.transaction_by_id(tx_id)?.expect("no transaction entry");
                             assert_eq!(Some(tx_id), provider.transaction_id(transaction.hash())?);
+                            assert_eq!(
+                                Some(StoredTransactionLocation {
+                                    block_number: number,
+                                    tx_index: tx_id - body.first_tx_num(),
+                                }),
+                                provider
+                                    .tx_ref()
+                                    .get::<tables::TransactionHashLocations>(transaction.hash())?
+                            );
                         }
                     }
                 }
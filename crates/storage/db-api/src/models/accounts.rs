@@ -1,14 +1,18 @@
 //! Account related models and types.
 
-use std::ops::{Range, RangeInclusive};
+use std::{
+    cmp::Ordering,
+    ops::{Range, RangeInclusive},
+};
 
 use crate::{
+    cursor::{DbCursorRO, RangeWalker},
     impl_fixed_arbitrary,
-    table::{Decode, Encode},
+    table::{Decode, Encode, Table},
     DatabaseError,
 };
-use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf, StorageKey};
+use reth_codecs::{derive_arbitrary, main_codec, Compact};
+use reth_primitives::{Account, Address, BlockNumber, Buf, StorageKey, U256};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved in the database.
@@ -57,6 +61,103 @@ impl Compact for AccountBeforeTx {
     }
 }
 
+// NOTE: Accounts are ordered by address first, which is also the order of the compact layout.
+// Ties are broken by the account info, where a missing account is ordered first.
+impl Ord for AccountBeforeTx {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let info = |info: &Option<Account>| {
+            info.map(|account| (account.nonce, account.balance, account.bytecode_hash))
+        };
+        self.address.cmp(&other.address).then_with(|| info(&self.info).cmp(&info(&other.info)))
+    }
+}
+
+impl PartialOrd for AccountBeforeTx {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Bitfield of the account fields changed by a transition.
+///
+/// It can be stored alongside the state of an [`AccountBeforeTx`] to skip the unchanged fields.
+#[main_codec]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct AccountChangeMask {
+    /// Whether the nonce changed.
+    pub nonce_changed: bool,
+    /// Whether the balance changed.
+    pub balance_changed: bool,
+    /// Whether the bytecode hash changed.
+    pub code_changed: bool,
+}
+
+impl AccountChangeMask {
+    /// Computes the fields changed between the account state before and after a transition.
+    ///
+    /// A missing account is treated as an empty account.
+    pub fn new(before: Option<&Account>, after: Option<&Account>) -> Self {
+        let before = before.copied().unwrap_or_default();
+        let after = after.copied().unwrap_or_default();
+        Self {
+            nonce_changed: before.nonce != after.nonce,
+            balance_changed: before.balance != after.balance,
+            code_changed: before.bytecode_hash != after.bytecode_hash,
+        }
+    }
+
+    /// Returns `true` if no field changed.
+    pub const fn is_empty(&self) -> bool {
+        !self.nonce_changed && !self.balance_changed && !self.code_changed
+    }
+}
+
+/// Storage slot as it is saved in the storage change set before a transaction.
+///
+/// [`StorageKey`] is the subkey.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct StorageBeforeTx {
+    /// Storage key of the slot. Acts as `DupSort::SubKey`.
+    pub storage_key: StorageKey,
+    /// Value of the slot before the transaction, `None` if the slot did not exist.
+    pub value: Option<U256>,
+}
+
+// NOTE: Like `AccountBeforeTx`, the subkey is encoded in full so that values can be fetched with
+// seek_by_key_subkey. The value is not compressed, since a compressed zero value would be
+// indistinguishable from a missing one.
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.storage_key.as_slice());
+
+        let mut value_len = 0;
+        if let Some(value) = self.value {
+            buf.put_slice(&value.to_be_bytes::<32>());
+            value_len = 32;
+        }
+        value_len + 32
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let storage_key = StorageKey::from_slice(&buf[..32]);
+        buf.advance(32);
+
+        let value = if len - 32 > 0 {
+            let value = U256::from_be_slice(&buf[..32]);
+            buf.advance(32);
+            Some(value)
+        } else {
+            None
+        };
+
+        (Self { storage_key, value }, buf)
+    }
+}
+
 /// [`BlockNumber`] concatenated with [`Address`].
 ///
 /// Since it's used as a key, it isn't compressed when encoding it.
@@ -73,6 +174,23 @@ impl BlockNumberAddress {
         (*range.start(), Address::ZERO).into()..(*range.end() + 1, Address::ZERO).into()
     }
 
+    /// Create a new Range from `start` to `end`
+    ///
+    /// Note: End is exclusive
+    pub fn range_exclusive(range: Range<BlockNumber>) -> Range<Self> {
+        (range.start, Address::ZERO).into()..(range.end, Address::ZERO).into()
+    }
+
+    /// Returns `true` if `key` falls inside the given range.
+    pub fn contains(range: &Range<Self>, key: &Self) -> bool {
+        range.start <= *key && *key < range.end
+    }
+
+    /// Returns `true` if the two ranges have at least one key in common.
+    pub fn overlaps(a: &Range<Self>, b: &Range<Self>) -> bool {
+        !a.is_empty() && !b.is_empty() && a.start < b.end && b.start < a.end
+    }
+
     /// Return the block number
     pub const fn block_number(&self) -> BlockNumber {
         self.0 .0
@@ -120,6 +238,63 @@ impl Decode for BlockNumberAddress {
     }
 }
 
+// NOTE: Uses the same byte layout as `Encode`, since it's used as a key it isn't compressed.
+impl Compact for BlockNumberAddress {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(&self.0 .0.to_be_bytes());
+        buf.put_slice(self.0 .1.as_slice());
+        28
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let num = buf.get_u64();
+        let address = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+
+        (Self((num, address)), buf)
+    }
+}
+
+/// Walks the entries of the given block range in a table keyed by [`BlockNumberAddress`], e.g. the
+/// storage change sets.
+///
+/// This is [`DbCursorRO::walk_range`] over [`BlockNumberAddress::range`].
+pub fn walk_block_range<T, C>(
+    cursor: &mut C,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<RangeWalker<'_, T, C>, DatabaseError>
+where
+    T: Table<Key = BlockNumberAddress>,
+    C: DbCursorRO<T>,
+{
+    cursor.walk_range(BlockNumberAddress::range(range))
+}
+
+/// Returns an iterator over the addresses touched in the given block, walking a table keyed by
+/// [`BlockNumberAddress`], e.g. the storage change sets.
+///
+/// Each address is yielded once, even if the table holds multiple entries for it.
+pub fn addresses_in_block<T, C>(
+    cursor: &mut C,
+    block: BlockNumber,
+) -> Result<impl Iterator<Item = Result<Address, DatabaseError>> + '_, DatabaseError>
+where
+    T: Table<Key = BlockNumberAddress>,
+    C: DbCursorRO<T>,
+{
+    let mut last_address = None;
+    Ok(walk_block_range(cursor, block..=block)?.filter_map(move |entry| match entry {
+        Ok((key, _)) => {
+            let address = key.address();
+            (last_address.replace(address) != Some(address)).then_some(Ok(address))
+        }
+        Err(err) => Some(Err(err)),
+    }))
+}
+
 /// [`Address`] concatenated with [`StorageKey`]. Used by `reth_etl` and history stages.
 ///
 /// Since it's used as a key, it isn't compressed when encoding it.
@@ -153,13 +328,101 @@ impl Decode for AddressStorageKey {
     }
 }
 
+impl From<(Address, StorageKey)> for AddressStorageKey {
+    fn from(tpl: (Address, StorageKey)) -> Self {
+        Self(tpl)
+    }
+}
+
+impl From<AddressStorageKey> for (Address, StorageKey) {
+    fn from(key: AddressStorageKey) -> Self {
+        key.0
+    }
+}
+
+// NOTE: Uses the same byte layout as `Encode`, since it's used as a key it isn't compressed.
+impl Compact for AddressStorageKey {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.0 .0.as_slice());
+        buf.put_slice(self.0 .1.as_slice());
+        52
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        let storage_key = StorageKey::from_slice(&buf[20..52]);
+        buf.advance(52);
+
+        (Self((address, storage_key)), buf)
+    }
+}
+
 impl_fixed_arbitrary!((BlockNumberAddress, 28), (AddressStorageKey, 52));
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
-    use std::str::FromStr;
+    use reth_primitives::U256;
+    use std::{collections::BTreeSet, str::FromStr};
+
+    #[test]
+    fn test_account_change_mask() {
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        assert!(AccountChangeMask::new(Some(&account), Some(&account)).is_empty());
+
+        let changed = Account { nonce: 2, ..account };
+        let mask = AccountChangeMask::new(Some(&account), Some(&changed));
+        assert_eq!(
+            mask,
+            AccountChangeMask { nonce_changed: true, balance_changed: false, code_changed: false }
+        );
+
+        let mut buf = vec![];
+        let len = mask.to_compact(&mut buf);
+        assert_eq!(AccountChangeMask::from_compact(&buf, len).0, mask);
+
+        let mask = AccountChangeMask::new(None, Some(&account));
+        assert!(mask.nonce_changed && mask.balance_changed && !mask.code_changed);
+    }
+
+    #[test]
+    fn test_account_before_tx_ord() {
+        let address = Address::from_str("ba5e000000000000000000000000000000000000").unwrap();
+        let destroyed = AccountBeforeTx { address, info: None };
+        let account = AccountBeforeTx { address, info: Some(Account::default()) };
+        let funded = AccountBeforeTx {
+            address,
+            info: Some(Account { balance: U256::from(1), ..Default::default() }),
+        };
+        let other = AccountBeforeTx { address: Address::repeat_byte(0xff), info: None };
+
+        let sorted = [other.clone(), funded.clone(), destroyed.clone(), account.clone()]
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![destroyed, account, funded, other]);
+    }
+
+    #[test]
+    fn test_storage_before_tx_compact() {
+        let storage_key = StorageKey::random();
+        for value in [None, Some(U256::ZERO), Some(U256::from(1337))] {
+            let entry = StorageBeforeTx { storage_key, value };
+
+            let mut buf = vec![];
+            let len = entry.clone().to_compact(&mut buf);
+            assert_eq!(len, if value.is_some() { 64 } else { 32 });
+
+            let (decoded, rest) = StorageBeforeTx::from_compact(&buf, len);
+            assert_eq!(decoded, entry);
+            assert!(rest.is_empty());
+        }
+    }
 
     #[test]
     fn test_block_number_address() {
@@ -178,6 +441,20 @@ mod tests {
         assert_eq!(decoded, key);
     }
 
+    #[test]
+    fn test_block_number_address_compact() {
+        let key = BlockNumberAddress((1337, Address::repeat_byte(0xba)));
+
+        let mut buf = vec![];
+        let len = key.to_compact(&mut buf);
+        assert_eq!(len, 28);
+        assert_eq!(buf, Encode::encode(key));
+
+        let (decoded, rest) = BlockNumberAddress::from_compact(&buf, len);
+        assert_eq!(decoded, key);
+        assert!(rest.is_empty());
+    }
+
     #[test]
     fn test_block_number_address_rand() {
         let mut bytes = [0u8; 28];
@@ -186,6 +463,31 @@ mod tests {
         assert_eq!(bytes, Encode::encode(key));
     }
 
+    #[test]
+    fn test_block_number_address_range_checks() {
+        let address = Address::from_str("ba5e000000000000000000000000000000000000").unwrap();
+        let range = BlockNumberAddress::range(1..=3);
+
+        assert!(!BlockNumberAddress::contains(&range, &BlockNumberAddress((0, address))));
+        assert!(BlockNumberAddress::contains(&range, &BlockNumberAddress((1, Address::ZERO))));
+        assert!(BlockNumberAddress::contains(&range, &BlockNumberAddress((3, address))));
+        assert!(!BlockNumberAddress::contains(&range, &BlockNumberAddress((4, Address::ZERO))));
+
+        assert!(BlockNumberAddress::overlaps(&range, &BlockNumberAddress::range(3..=5)));
+        assert!(BlockNumberAddress::overlaps(&range, &BlockNumberAddress::range(0..=1)));
+        assert!(!BlockNumberAddress::overlaps(&range, &BlockNumberAddress::range(4..=5)));
+        assert!(!BlockNumberAddress::overlaps(
+            &range,
+            &((2, Address::ZERO).into()..(2, Address::ZERO).into())
+        ));
+    }
+
+    #[test]
+    fn test_block_number_address_range_exclusive() {
+        assert_eq!(BlockNumberAddress::range_exclusive(1..4), BlockNumberAddress::range(1..=3));
+        assert!(BlockNumberAddress::range_exclusive(0..0).is_empty());
+    }
+
     #[test]
     fn test_address_storage_key() {
         let storage_key = StorageKey::random();
@@ -203,6 +505,30 @@ mod tests {
         assert_eq!(decoded, key);
     }
 
+    #[test]
+    fn test_address_storage_key_from_tuple() {
+        let tpl = (Address::repeat_byte(0xba), StorageKey::random());
+        let key = AddressStorageKey::from(tpl);
+        assert_eq!(key, AddressStorageKey(tpl));
+        assert_eq!(<(Address, StorageKey)>::from(key), tpl);
+    }
+
+    #[test]
+    fn test_address_storage_key_compact() {
+        let storage_key = StorageKey::random();
+        let address = Address::from_str("ba5e000000000000000000000000000000000000").unwrap();
+        let key = AddressStorageKey((address, storage_key));
+
+        let mut buf = vec![];
+        let len = key.to_compact(&mut buf);
+        assert_eq!(len, 52);
+        assert_eq!(buf, Encode::encode(key));
+
+        let (decoded, rest) = AddressStorageKey::from_compact(&buf, len);
+        assert_eq!(decoded, key);
+        assert!(rest.is_empty());
+    }
+
     #[test]
     fn test_address_storage_key_rand() {
         let mut bytes = [0u8; 52];
@@ -1,7 +1,7 @@
 //! Block related models and types.
 
 use reth_codecs::{main_codec, Compact};
-use reth_primitives::{Header, TxNumber, Withdrawals, B256};
+use reth_primitives::{BlockNumber, Header, TxNumber, Withdrawals, B256};
 use std::ops::Range;
 
 /// Total number of transactions.
@@ -83,6 +83,19 @@ pub struct StoredBlockWithdrawals {
     pub withdrawals: Withdrawals,
 }
 
+/// The location of a transaction in the chain.
+///
+/// It points to the block the transaction was included in and the index of the transaction in
+/// that block.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct StoredTransactionLocation {
+    /// The number of the block the transaction was included in.
+    pub block_number: BlockNumber,
+    /// The index of the transaction in the block.
+    pub tx_index: u64,
+}
+
 /// Hash of the block header.
 pub type HeaderHash = B256;
 
@@ -102,6 +115,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transaction_location() {
+        let location = StoredTransactionLocation { block_number: 100, tx_index: 3 };
+        assert_eq!(location, StoredTransactionLocation::decompress(location.compress()).unwrap());
+    }
+
     #[test]
     fn block_indices() {
         let first_tx_num = 10;
@@ -73,7 +73,7 @@ impl Encode for Address {
 
 impl Decode for Address {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
-        Ok(Self::from_slice(value.as_ref()))
+        Ok(Self::new(value.as_ref().try_into().map_err(|_| DatabaseError::Decode)?))
     }
 }
 
@@ -214,8 +214,10 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
+    StoredTransactionLocation,
     Bytecode,
     AccountBeforeTx,
+    StorageBeforeTx,
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
@@ -309,6 +311,13 @@ mod tests {
         StorageHashingCheckpoint,
     };
 
+    #[test]
+    fn test_address_encode_decode() {
+        let address = Address::with_last_byte(42);
+        assert_eq!(Address::decode(address.encode()).unwrap(), address);
+        assert!(matches!(Address::decode([0u8; 19]), Err(DatabaseError::Decode)));
+    }
+
     // each value in the database has an extra field named flags that encodes metadata about other
     // fields in the value, e.g. offset and length.
     //
@@ -318,6 +327,7 @@ mod tests {
     #[test]
     fn test_ensure_backwards_compatibility() {
         assert_eq!(Account::bitflag_encoded_bytes(), 2);
+        assert_eq!(AccountChangeMask::bitflag_encoded_bytes(), 1);
         assert_eq!(AccountHashingCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(CheckpointBlockRange::bitflag_encoded_bytes(), 1);
         assert_eq!(CompactClientVersion::bitflag_encoded_bytes(), 0);
@@ -339,6 +349,7 @@ mod tests {
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+        assert_eq!(StoredTransactionLocation::bitflag_encoded_bytes(), 1);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(TxEip1559::bitflag_encoded_bytes(), 4);
         assert_eq!(TxEip2930::bitflag_encoded_bytes(), 3);
@@ -351,6 +362,7 @@ mod tests {
     #[test]
     fn test_ensure_backwards_compatibility() {
         assert_eq!(Account::bitflag_encoded_bytes(), 2);
+        assert_eq!(AccountChangeMask::bitflag_encoded_bytes(), 1);
         assert_eq!(AccountHashingCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(CheckpointBlockRange::bitflag_encoded_bytes(), 1);
         assert_eq!(CompactClientVersion::bitflag_encoded_bytes(), 0);
@@ -372,6 +384,7 @@ mod tests {
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+        assert_eq!(StoredTransactionLocation::bitflag_encoded_bytes(), 1);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(TxEip1559::bitflag_encoded_bytes(), 4);
         assert_eq!(TxEip2930::bitflag_encoded_bytes(), 3);
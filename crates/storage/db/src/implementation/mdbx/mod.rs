@@ -474,11 +474,11 @@ mod tests {
             AccountsHistory, CanonicalHeaders, Headers, PlainAccountState, PlainStorageState,
         },
         test_utils::*,
-        AccountChangeSets,
+        AccountChangeSets, StorageChangeSets,
     };
     use reth_db_api::{
         cursor::{DbDupCursorRO, DbDupCursorRW, ReverseWalker, Walker},
-        models::{AccountBeforeTx, ShardedKey},
+        models::{addresses_in_block, walk_block_range, AccountBeforeTx, ShardedKey},
         table::{Encode, Table},
     };
     use reth_libmdbx::Error;
@@ -708,6 +708,61 @@ mod tests {
         assert_eq!(walker.next(), None);
     }
 
+    #[test]
+    fn db_addresses_in_block() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+
+        let address0 = Address::with_last_byte(1);
+        let address1 = Address::with_last_byte(2);
+        let entry = |key| StorageEntry { key, value: U256::from(1) };
+
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        for (block, address, key) in [
+            (1, address0, B256::with_last_byte(1)),
+            (1, address0, B256::with_last_byte(2)),
+            (1, address1, B256::with_last_byte(1)),
+            (2, address0, B256::with_last_byte(1)),
+        ] {
+            tx.put::<StorageChangeSets>((block, address).into(), entry(key)).expect(ERROR_PUT);
+        }
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<StorageChangeSets>().unwrap();
+
+        let addresses =
+            addresses_in_block(&mut cursor, 1).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(addresses, vec![address0, address1]);
+
+        let addresses =
+            addresses_in_block(&mut cursor, 3).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(addresses.is_empty());
+    }
+
+    #[test]
+    fn db_walk_block_range() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+
+        let address = Address::with_last_byte(1);
+        let entry = StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) };
+
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        for block in 0..4 {
+            tx.put::<StorageChangeSets>((block, address).into(), entry).expect(ERROR_PUT);
+        }
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<StorageChangeSets>().unwrap();
+
+        let blocks = walk_block_range(&mut cursor, 1..=2)
+            .unwrap()
+            .map(|entry| entry.map(|(key, _)| key.block_number()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(blocks, vec![1, 2]);
+    }
+
     #[allow(clippy::reversed_empty_ranges)]
     #[test]
     fn db_cursor_walk_range_invalid() {
@@ -25,6 +25,7 @@ use reth_db_api::{
         client_version::ClientVersion,
         storage_sharded_key::StorageShardedKey,
         CompactU256, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+        StoredTransactionLocation,
     },
     table::{Decode, DupSort, Encode, Table},
 };
@@ -298,6 +299,20 @@ tables! {
     /// Stores the mapping of the transaction hash to the transaction number.
     table TransactionHashNumbers<Key = TxHash, Value = TxNumber>;
 
+    /// Stores the mapping of the transaction hash to the block number and the index of the
+    /// transaction in that block. It is named after its value, the location of the transaction,
+    /// rather than only the block number.
+    ///
+    /// Secondary index of [`TransactionHashNumbers`], written along with it by the transaction
+    /// lookup stage and when inserting blocks, and pruned along with it. Transaction lookups by
+    /// hash read the location from this table and fall back to [`TransactionHashNumbers`] when
+    /// it has no entry.
+    ///
+    /// The table is created empty when an existing database is opened. Nodes synced before this
+    /// table existed need to drop and re-run the transaction lookup stage
+    /// (`reth stage drop tx-lookup`) to backfill it.
+    table TransactionHashLocations<Key = TxHash, Value = StoredTransactionLocation>;
+
     /// Stores the mapping of transaction number to the blocks number.
     ///
     /// The key is the highest transaction ID in the block.
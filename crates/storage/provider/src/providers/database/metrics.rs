@@ -60,6 +60,7 @@ pub(crate) enum Action {
     InsertTransactionSenders,
     InsertTransactions,
     InsertTransactionHashNumbers,
+    InsertTransactionHashLocations,
     InsertBlockWithdrawals,
     InsertBlockRequests,
     InsertBlockBodyIndices,
@@ -104,6 +105,8 @@ struct DatabaseProviderMetrics {
     insert_transactions: Histogram,
     /// Duration of insert transaction hash numbers
     insert_tx_hash_numbers: Histogram,
+    /// Duration of insert transaction hash locations
+    insert_tx_hash_locations: Histogram,
     /// Duration of insert block withdrawals
     insert_block_withdrawals: Histogram,
     /// Duration of insert block requests
@@ -138,6 +141,9 @@ impl DatabaseProviderMetrics {
             Action::InsertTransactionSenders => self.insert_tx_senders.record(duration),
             Action::InsertTransactions => self.insert_transactions.record(duration),
             Action::InsertTransactionHashNumbers => self.insert_tx_hash_numbers.record(duration),
+            Action::InsertTransactionHashLocations => {
+                self.insert_tx_hash_locations.record(duration)
+            }
             Action::InsertBlockWithdrawals => self.insert_block_withdrawals.record(duration),
             Action::InsertBlockRequests => self.insert_block_requests.record(duration),
             Action::InsertBlockBodyIndices => self.insert_block_body_indices.record(duration),
@@ -596,6 +596,7 @@ mod tests {
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
+    use reth_db_api::transaction::DbTxMut;
     use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
@@ -689,6 +690,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transaction_by_hash_with_meta_from_locations() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), None);
+
+        let provider = factory.provider_rw().unwrap();
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        for (index, transaction) in block.body.iter().enumerate() {
+            let (_, meta) =
+                provider.transaction_by_hash_with_meta(transaction.hash).unwrap().unwrap();
+            assert_eq!(meta.index, index as u64);
+            assert_eq!(meta.block_hash, block.hash());
+
+            // without the secondary index, the transaction number lookup resolves the same meta
+            provider
+                .tx_ref()
+                .delete::<tables::TransactionHashLocations>(transaction.hash, None)
+                .unwrap();
+            assert_eq!(
+                provider.transaction_by_hash_with_meta(transaction.hash).unwrap(),
+                Some((transaction.clone(), meta))
+            );
+        }
+    }
+
     #[test]
     fn get_take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -21,8 +21,9 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, RangeWalker},
     database::Database,
     models::{
-        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        sharded_key, storage_sharded_key::StorageShardedKey, walk_block_range, AccountBeforeTx,
+        BlockNumberAddress, ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers,
+        StoredBlockWithdrawals, StoredTransactionLocation,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
@@ -879,12 +880,17 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
 
         if TAKE {
-            // Remove TransactionHashNumbers
+            // Remove TransactionHashNumbers and TransactionHashLocations
             let mut tx_hash_cursor = self.tx.cursor_write::<tables::TransactionHashNumbers>()?;
+            let mut tx_location_cursor =
+                self.tx.cursor_write::<tables::TransactionHashLocations>()?;
             for (_, tx) in &transactions {
                 if tx_hash_cursor.seek_exact(tx.hash())?.is_some() {
                     tx_hash_cursor.delete_current()?;
                 }
+                if tx_location_cursor.seek_exact(tx.hash())?.is_some() {
+                    tx_location_cursor.delete_current()?;
+                }
             }
 
             // Remove TransactionBlocks index if there are transaction present
@@ -1809,39 +1815,48 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         &self,
         tx_hash: TxHash,
     ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>> {
-        let mut transaction_cursor = self.tx.cursor_read::<tables::TransactionBlocks>()?;
-        if let Some(transaction_id) = self.transaction_id(tx_hash)? {
-            if let Some(tx) = self.transaction_by_id_no_hash(transaction_id)? {
-                let transaction = TransactionSigned {
-                    hash: tx_hash,
-                    signature: tx.signature,
-                    transaction: tx.transaction,
+        let (block_number, transaction_id) =
+            if let Some(location) = self.tx.get::<tables::TransactionHashLocations>(tx_hash)? {
+                // the secondary index resolves the block of the transaction directly
+                let Some(block_body) = self.block_body_indices(location.block_number)? else {
+                    return Ok(None)
                 };
-                if let Some(block_number) =
-                    transaction_cursor.seek(transaction_id).map(|b| b.map(|(_, bn)| bn))?
-                {
-                    if let Some(sealed_header) = self.sealed_header(block_number)? {
-                        let (header, block_hash) = sealed_header.split();
-                        if let Some(block_body) = self.block_body_indices(block_number)? {
-                            // the index of the tx in the block is the offset:
-                            // len([start..tx_id])
-                            // NOTE: `transaction_id` is always `>=` the block's first
-                            // index
-                            let index = transaction_id - block_body.first_tx_num();
-
-                            let meta = TransactionMeta {
-                                tx_hash,
-                                index,
-                                block_hash,
-                                block_number,
-                                base_fee: header.base_fee_per_gas,
-                                excess_blob_gas: header.excess_blob_gas,
-                                timestamp: header.timestamp,
-                            };
-
-                            return Ok(Some((transaction, meta)))
-                        }
-                    }
+                (location.block_number, block_body.first_tx_num() + location.tx_index)
+            } else {
+                // the secondary index is not populated on nodes synced before it existed
+                let Some(transaction_id) = self.transaction_id(tx_hash)? else { return Ok(None) };
+                let Some(block_number) = self.transaction_block(transaction_id)? else {
+                    return Ok(None)
+                };
+                (block_number, transaction_id)
+            };
+
+        if let Some(tx) = self.transaction_by_id_no_hash(transaction_id)? {
+            let transaction = TransactionSigned {
+                hash: tx_hash,
+                signature: tx.signature,
+                transaction: tx.transaction,
+            };
+            if let Some(sealed_header) = self.sealed_header(block_number)? {
+                let (header, block_hash) = sealed_header.split();
+                if let Some(block_body) = self.block_body_indices(block_number)? {
+                    // the index of the tx in the block is the offset:
+                    // len([start..tx_id])
+                    // NOTE: `transaction_id` is always `>=` the block's first
+                    // index
+                    let index = transaction_id - block_body.first_tx_num();
+
+                    let meta = TransactionMeta {
+                        tx_hash,
+                        index,
+                        block_hash,
+                        block_number,
+                        base_fee: header.base_fee_per_gas,
+                        excess_blob_gas: header.excess_blob_gas,
+                        timestamp: header.timestamp,
+                    };
+
+                    return Ok(Some((transaction, meta)))
                 }
             }
         }
@@ -2050,7 +2065,7 @@ impl<TX: DbTx> EvmEnvProvider for DatabaseProvider<TX> {
             block_env,
             &self.chain_spec,
             header,
-            total_difficulty,
+            Some(total_difficulty),
         );
         Ok(())
     }
@@ -2073,7 +2088,7 @@ impl<TX: DbTx> EvmEnvProvider for DatabaseProvider<TX> {
         &self,
         cfg: &mut CfgEnvWithHandlerCfg,
         header: &Header,
-        _evm_config: EvmConfig,
+        evm_config: EvmConfig,
     ) -> ProviderResult<()>
     where
         EvmConfig: ConfigureEvmEnv,
@@ -2081,7 +2096,7 @@ impl<TX: DbTx> EvmEnvProvider for DatabaseProvider<TX> {
         let total_difficulty = self
             .header_td_by_number(header.number)?
             .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
-        EvmConfig::fill_cfg_env(cfg, &self.chain_spec, header, total_difficulty);
+        EvmConfig::fill_cfg_env(cfg, &self.chain_spec, header, Some(total_difficulty));
         Ok(())
     }
 }
@@ -2185,7 +2200,7 @@ impl<TX: DbTx> StorageReader for DatabaseProvider<TX> {
         let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
 
         let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
+            walk_block_range(&mut changeset_cursor, range)?.try_fold(
                 BTreeMap::new(),
                 |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
                     let (index, storage) = entry?;
@@ -2674,6 +2689,7 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         let mut tx_senders_elapsed = Duration::default();
         let mut transactions_elapsed = Duration::default();
         let mut tx_hash_numbers_elapsed = Duration::default();
+        let mut tx_hash_locations_elapsed = Duration::default();
 
         for (transaction, sender) in block.block.body.into_iter().zip(block.senders.iter()) {
             let hash = transaction.hash();
@@ -2711,6 +2727,16 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
                 let start = Instant::now();
                 self.tx.put::<tables::TransactionHashNumbers>(hash, next_tx_num)?;
                 tx_hash_numbers_elapsed += start.elapsed();
+
+                let start = Instant::now();
+                self.tx.put::<tables::TransactionHashLocations>(
+                    hash,
+                    StoredTransactionLocation {
+                        block_number,
+                        tx_index: next_tx_num - first_tx_num,
+                    },
+                )?;
+                tx_hash_locations_elapsed += start.elapsed();
             }
             next_tx_num += 1;
         }
@@ -2722,6 +2748,10 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             metrics::Action::InsertTransactionHashNumbers,
             tx_hash_numbers_elapsed,
         );
+        durations_recorder.record_duration(
+            metrics::Action::InsertTransactionHashLocations,
+            tx_hash_locations_elapsed,
+        );
 
         if let Some(withdrawals) = block.block.withdrawals {
             if !withdrawals.is_empty() {
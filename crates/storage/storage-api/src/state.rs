@@ -7,6 +7,7 @@ use reth_primitives::{
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::AccountProof;
+use std::ops::Range;
 
 /// Type alias of boxed [`StateProvider`].
 pub type StateProviderBox = Box<dyn StateProvider>;
@@ -152,6 +153,20 @@ pub trait StateProviderFactory: BlockIdReader + Send + Sync {
     /// Note: this only looks at historical blocks, not pending blocks.
     fn history_by_block_number(&self, block: BlockNumber) -> ProviderResult<StateProviderBox>;
 
+    /// Returns an iterator over historical [StateProvider]s for all block numbers in the given
+    /// range, e.g. to replay a range of blocks.
+    ///
+    /// The providers are created lazily, so only the providers that are currently in use hold an
+    /// open database transaction.
+    ///
+    /// Note: this only looks at historical blocks, not pending blocks.
+    fn state_by_block_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> Box<dyn Iterator<Item = ProviderResult<StateProviderBox>> + '_> {
+        Box::new(range.map(|block| self.history_by_block_number(block)))
+    }
+
     /// Returns a historical [StateProvider] indexed by the given block hash.
     ///
     /// Note: this only looks at historical blocks, not pending blocks.
@@ -18,8 +18,9 @@ use reth::{
     },
     tasks::TaskManager,
 };
-use reth_chainspec::{Chain, ChainSpec, Head};
-use reth_node_api::{ConfigureEvm, ConfigureEvmEnv, FullNodeTypes};
+use reth_chainspec::{Chain, ChainSpec};
+use reth_evm::head_for_header;
+use reth_node_api::{ChainSpecLike, ConfigureEvm, ConfigureEvmEnv, FullNodeTypes};
 use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
 use reth_node_ethereum::{EthExecutorProvider, EthereumNode};
 use reth_primitives::{
@@ -27,7 +28,7 @@ use reth_primitives::{
     Header, U256,
 };
 use reth_tracing::{RethTracer, Tracer};
-use std::sync::Arc;
+use std::{convert::Infallible, sync::Arc};
 
 /// Custom EVM configuration
 #[derive(Debug, Clone, Copy, Default)]
@@ -66,21 +67,17 @@ impl MyEvmConfig {
 }
 
 impl ConfigureEvmEnv for MyEvmConfig {
+    type Error = Infallible;
+
     fn fill_cfg_env(
         cfg_env: &mut CfgEnvWithHandlerCfg,
-        chain_spec: &ChainSpec,
+        chain_spec: &dyn ChainSpecLike,
         header: &Header,
-        total_difficulty: U256,
+        total_difficulty: Option<U256>,
     ) {
-        let spec_id = reth_evm_ethereum::revm_spec(
-            chain_spec,
-            &Head {
-                number: header.number,
-                timestamp: header.timestamp,
-                difficulty: header.difficulty,
-                total_difficulty,
-                hash: Default::default(),
-            },
+        let spec_id = reth_evm_ethereum::revm_spec_by_hardforks(
+            chain_spec.hardforks(),
+            &head_for_header(header, total_difficulty),
         );
 
         cfg_env.chain_id = chain_spec.chain().id();
@@ -88,6 +85,11 @@ impl ConfigureEvmEnv for MyEvmConfig {
 
         cfg_env.handler_cfg.spec_id = spec_id;
     }
+
+    fn chain_id(&self) -> u64 {
+        // the chain spec of this example uses the mainnet chain id
+        Chain::mainnet().id()
+    }
 }
 
 impl ConfigureEvm for MyEvmConfig {
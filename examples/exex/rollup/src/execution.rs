@@ -111,9 +111,9 @@ fn configure_evm<'a>(
     EthEvmConfig::fill_cfg_and_block_env(
         &mut cfg,
         evm.block_mut(),
-        &CHAIN_SPEC,
+        &*CHAIN_SPEC,
         header,
-        U256::ZERO,
+        Some(U256::ZERO),
     );
     *evm.cfg_mut() = cfg.cfg_env;
 
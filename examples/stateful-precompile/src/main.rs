@@ -19,7 +19,7 @@ use reth::{
     tasks::TaskManager,
 };
 use reth_chainspec::{Chain, ChainSpec};
-use reth_node_api::{ConfigureEvm, ConfigureEvmEnv, FullNodeTypes};
+use reth_node_api::{ChainSpecLike, ConfigureEvm, ConfigureEvmEnv, FullNodeTypes};
 use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
 use reth_node_ethereum::{EthEvmConfig, EthExecutorProvider, EthereumNode};
 use reth_primitives::{
@@ -28,7 +28,7 @@ use reth_primitives::{
 };
 use reth_tracing::{RethTracer, Tracer};
 use schnellru::{ByLength, LruMap};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
 
 /// A cache for precompile inputs / outputs.
 ///
@@ -133,18 +133,24 @@ impl StatefulPrecompileMut for WrappedPrecompile {
 }
 
 impl ConfigureEvmEnv for MyEvmConfig {
+    type Error = Infallible;
+
     fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
         EthEvmConfig::default().fill_tx_env(tx_env, transaction, sender)
     }
 
     fn fill_cfg_env(
         cfg_env: &mut CfgEnvWithHandlerCfg,
-        chain_spec: &ChainSpec,
+        chain_spec: &dyn ChainSpecLike,
         header: &Header,
-        total_difficulty: U256,
+        total_difficulty: Option<U256>,
     ) {
         EthEvmConfig::fill_cfg_env(cfg_env, chain_spec, header, total_difficulty)
     }
+
+    fn chain_id(&self) -> u64 {
+        EthEvmConfig::default().chain_id()
+    }
 }
 
 impl ConfigureEvm for MyEvmConfig {